@@ -1,26 +1,80 @@
 use std::{
+    collections::BTreeMap,
     env::var,
-    fs::{read_dir, read_to_string, File},
+    ffi::OsStr,
+    fs::{read, read_dir, read_to_string, File},
     io::Write,
+    iter::once,
+    path::{Path, PathBuf},
 };
 
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use check::digest;
+use chrono::{Local, NaiveDate};
+use generate::generate;
+use grid::{cells, Vec2};
+use import::{ipuz_to_crossword, puz_to_crossword};
 use itertools::Itertools;
 use proc_macro2::TokenTree;
+use sha2::{Digest, Sha256};
+use sudoku::Sudoku;
 use syn::{parse_file, Item, ItemFn, Signature, Stmt, StmtMacro};
+use word_search::WordSearch;
+
+#[path = "src/crossword/grid.rs"]
+#[allow(dead_code)]
+mod grid;
+
+#[path = "src/crossword/generate.rs"]
+mod generate;
+
+#[path = "src/crossword/check.rs"]
+mod check;
+
+#[path = "src/crossword/import.rs"]
+mod import;
+
+#[path = "src/puzzle/sudoku.rs"]
+#[allow(dead_code)]
+mod sudoku;
+
+#[path = "src/puzzle/word_search.rs"]
+#[allow(dead_code)]
+mod word_search;
+
+/// Lets the puzzle modules find `Vec2` where they would in the app.
+mod crossword {
+    pub use super::grid::Vec2;
+}
+
+const AD_FORMATS: [&str; 4] = ["horizontal", "vertical", "square", "inline"];
+/// Where `examples/cache_images.rs` keeps resized copies of article images,
+/// served from `/images/cache`.
+const IMAGE_CACHE: &str = "src/images/cache";
 
 fn main() {
     println!("cargo:rerun-if-changed=src/articles");
     println!("cargo:rerun-if-changed=src/crosswords");
-    println!("cargo:rerun-if-changed=src/images/ads");
+    for format in AD_FORMATS {
+        println!("cargo:rerun-if-changed=src/images/{format}-ads");
+    }
+    println!("cargo:rerun-if-changed=src/ads");
+    println!("cargo:rerun-if-changed=src/puzzles");
+    println!("cargo:rerun-if-changed=src/quizzes");
+    println!("cargo:rerun-if-changed={IMAGE_CACHE}");
     let article_ids = collect_articles();
+    collect_images();
     collect_ads();
     let crosswords = collect_crosswords();
-    generate_sitemap(&article_ids, crosswords);
+    let puzzles = collect_puzzles(&article_ids);
+    collect_quiz(&article_ids);
+    generate_sitemap(&article_ids, crosswords, &puzzles);
 
     dbg!(var("OUT_DIR").unwrap());
 }
 
-fn generate_sitemap(article_ids: &[String], crosswords: usize) {
+fn generate_sitemap(article_ids: &[String], crosswords: usize, puzzles: &[(String, String)]) {
     let sitemap = parse_file(include_str!("src/components.rs"))
         .unwrap()
         .items
@@ -97,6 +151,13 @@ fn generate_sitemap(article_ids: &[String], crosswords: usize) {
             "/crosswords/:id" => (0..crosswords)
                 .map(|id| format!("/crosswords/{id}"))
                 .collect_vec(),
+            "/crosswords/:id/print" => (0..crosswords)
+                .map(|id| format!("/crosswords/{id}/print"))
+                .collect_vec(),
+            "/puzzles/:kind/:id" => puzzles
+                .iter()
+                .map(|(kind, id)| format!("/puzzles/{kind}/{id}"))
+                .collect_vec(),
             "/*" => Vec::new(),
             _ => {
                 vec![route.clone()]
@@ -110,29 +171,369 @@ fn generate_sitemap(article_ids: &[String], crosswords: usize) {
         .unwrap();
 }
 
+/// Embeds every crossword released on or before the day of the build, oldest
+/// first, so a puzzle's id doesn't change as later ones are released. Puzzles
+/// without a `!date YYYY-MM-DD` line count as released before all others. The
+/// site has to be rebuilt each day for new puzzles to appear.
 fn collect_crosswords() -> usize {
+    let today = Local::now().date_naive();
     let crosswords = read_dir("src/crosswords")
         .unwrap()
         .map(|entry| {
-            read_to_string(entry.unwrap().path())
-                .unwrap()
-                .trim()
-                .to_string()
+            let path = entry.unwrap().path();
+            let crossword = match path.extension().and_then(OsStr::to_str) {
+                Some("ipuz") => ipuz_to_crossword(&read_to_string(&path).unwrap()),
+                Some("puz") => puz_to_crossword(&read(&path).unwrap()),
+                Some("clues") => clues_to_crossword(&read_to_string(&path).unwrap()),
+                _ => read_to_string(&path).unwrap().trim().to_string(),
+            };
+            let date = crossword.lines().find_map(|line| {
+                line.strip_prefix("!date ")
+                    .map(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").unwrap())
+            });
+            (date, path, crossword)
         })
+        .filter(|(date, _, _)| date.is_none_or(|date| date <= today))
+        .sorted_unstable_by(|(a_date, a_path, _), (b_date, b_path, _)| {
+            a_date.cmp(b_date).then_with(|| {
+                let number = |path: &PathBuf| {
+                    path.file_stem()
+                        .and_then(OsStr::to_str)
+                        .and_then(|stem| stem.parse::<usize>().ok())
+                };
+                number(a_path)
+                    .cmp(&number(b_path))
+                    .then_with(|| a_path.cmp(b_path))
+            })
+        })
+        .map(|(_, path, crossword)| seal(&crossword, &path))
         .collect_vec();
     File::create(var("OUT_DIR").unwrap() + "/crosswords")
         .unwrap()
-        .write_all(
-            &crosswords
-                .join("\n\n")
-                .bytes()
-                .map(u8::reverse_bits)
-                .collect_vec(),
-        )
+        .write_all(crosswords.join("\n\n").as_bytes())
         .unwrap();
     crosswords.len()
 }
 
+/// Replaces each answer with its pattern of `?` cells and separators and a
+/// salted digest, so the bundle holds the grid's shape and clues but never the
+/// answers. A `!cellcheck` line also adds a digest for every cell so single
+/// letters can be checked. Each salt is derived from the file and the word or
+/// cell, so the same sources always build the same bundle and no two entries
+/// share a hash. The salts ship alongside the digests, so this only keeps the
+/// answers from being read straight out of the bundle: with the length known
+/// from the pattern, any answer can be brute forced offline.
+fn seal(crossword: &str, path: &Path) -> String {
+    let path = path.to_string_lossy();
+    let salt = |key: &str| digest(&path, &[key]);
+    let (extras, words): (Vec<_>, Vec<_>) =
+        crossword.lines().partition(|line| line.starts_with('!'));
+    let mut letters = BTreeMap::new();
+    let words = words
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let mut parts = line.splitn(5, char::is_whitespace);
+            let answer = parts.next().unwrap();
+            let x: usize = parts.next().unwrap().parse().unwrap();
+            let y: usize = parts.next().unwrap().parse().unwrap();
+            let direction = parts.next().unwrap();
+            let clue = parts.next().unwrap();
+            let cells = cells(answer);
+            for (index, cell) in cells.iter().enumerate() {
+                let position = match direction {
+                    "across" => (y, x + index),
+                    "down" => (y + index, x),
+                    _ => unreachable!(),
+                };
+                letters.insert(position, cell.clone());
+            }
+            let mut in_rebus = false;
+            let pattern: String = answer
+                .chars()
+                .filter_map(|char| match char {
+                    '[' => {
+                        in_rebus = true;
+                        Some('?')
+                    }
+                    ']' => {
+                        in_rebus = false;
+                        None
+                    }
+                    _ if in_rebus => None,
+                    '_' | '-' => Some(char),
+                    _ => Some('?'),
+                })
+                .collect();
+            let salt = salt(&index.to_string());
+            let hash = digest(&salt, &cells.iter().map(String::as_str).collect_vec());
+            format!("{pattern} {x} {y} {direction} {salt} {hash} {clue}")
+        })
+        .collect_vec();
+    let digits = letters
+        .values()
+        .any(|cell| cell.chars().any(|char| char.is_ascii_digit()))
+        .then(|| "!digits".to_string());
+    let cell_checks = extras
+        .contains(&"!cellcheck")
+        .then(|| {
+            letters.iter().map(|((y, x), cell)| {
+                let salt = salt(&format!("{x} {y}"));
+                format!("!cell {x} {y} {salt} {}", digest(&salt, &[cell]))
+            })
+        })
+        .into_iter()
+        .flatten()
+        .collect_vec();
+    words
+        .into_iter()
+        .chain(
+            extras
+                .into_iter()
+                .filter(|line| *line != "!cellcheck")
+                .map(str::to_string),
+        )
+        .chain(digits)
+        .chain(cell_checks)
+        .join("\n")
+}
+
+/// Lays out a setter's `answer clue` lines into a grid. An optional first line
+/// of `seed <number>` picks a different layout for the same clues, and lines
+/// starting with `!`, such as `!date`, are passed through unchanged.
+fn clues_to_crossword(data: &str) -> String {
+    let mut lines = data.trim().lines().peekable();
+    let seed = lines
+        .next_if(|line| line.starts_with("seed "))
+        .map_or(0, |line| line["seed ".len()..].trim().parse().unwrap());
+    let (extras, lines): (Vec<_>, Vec<_>) = lines.partition(|line| line.starts_with('!'));
+    let entries = lines
+        .into_iter()
+        .map(|line| {
+            let (answer, clue) = line.split_once(char::is_whitespace).unwrap();
+            (
+                &*answer.to_lowercase().leak(),
+                &*clue.trim().to_string().leak(),
+            )
+        })
+        .collect_vec();
+    generate(&entries, seed)
+        .expect("answers could not all be fitted into one connected grid; check every answer shares a letter with another")
+        .iter()
+        .map(|word| {
+            format!(
+                "{} {} {} {} {}",
+                word.answer,
+                word.position.x,
+                word.position.y,
+                word.direction.to_string().to_lowercase(),
+                word.clue
+            )
+        })
+        .chain(extras.into_iter().map(str::to_string))
+        .join("\n")
+}
+
+/// Collects `src/puzzles/<kind>/<id>` files into length-prefixed records of
+/// `kind id` and the file's contents, returning each kind and id.
+fn collect_puzzles(article_ids: &[String]) -> Vec<(String, String)> {
+    const KINDS: [&str; 3] = ["wordsearch", "headline", "sudoku"];
+    let (records, puzzles): (Vec<_>, Vec<_>) = read_dir("src/puzzles")
+        .unwrap()
+        .flat_map(|kind_entry| {
+            let kind_entry = kind_entry.unwrap();
+            let kind = kind_entry.file_name().to_string_lossy().to_string();
+            assert!(KINDS.contains(&kind.as_str()), "unknown puzzle kind {kind}");
+            read_dir(kind_entry.path())
+                .unwrap()
+                .map(move |entry| {
+                    let entry = entry.unwrap();
+                    let id = entry.file_name().to_string_lossy().to_string();
+                    let body = read_to_string(entry.path()).unwrap();
+                    let body = body.trim();
+                    match kind.as_str() {
+                        "headline" => check_headline(&id, body, article_ids),
+                        "sudoku" => check_sudoku(&id, body),
+                        "wordsearch" => check_word_search(&id, body),
+                        _ => unreachable!(),
+                    }
+                    let data = format!("{kind} {id}\n{body}");
+                    (format!("{} {}", data.len(), data), (kind.clone(), id))
+                })
+                .collect_vec()
+        })
+        .sorted_unstable_by(|(_, a), (_, b)| a.cmp(b))
+        .unzip();
+    File::create(var("OUT_DIR").unwrap() + "/puzzles")
+        .unwrap()
+        .write_all(records.join("\n").as_bytes())
+        .unwrap();
+    puzzles
+}
+
+/// Panics unless a headline puzzle names an article and each word it hides is
+/// in that article's title, compared as the game does, ignoring case and
+/// punctuation.
+fn check_headline(id: &str, body: &str, article_ids: &[String]) {
+    let mut lines = body.lines();
+    let article = lines.next().unwrap().trim();
+    assert!(
+        article_ids.iter().any(|id| id == article),
+        "headline puzzle {id} refers to unknown article {article}"
+    );
+    let title = read_dir("src/articles")
+        .unwrap()
+        .map(|topic| topic.unwrap().path().join(article))
+        .find_map(|path| read_to_string(path).ok())
+        .unwrap()
+        .lines()
+        .nth(1)
+        .unwrap()
+        .to_string();
+    let normalise = |word: &str| {
+        word.chars()
+            .filter(|char| char.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let words = title.split_whitespace().map(normalise).collect_vec();
+    let hidden = lines
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect_vec();
+    assert!(!hidden.is_empty(), "headline puzzle {id} hides no words");
+    for word in hidden {
+        assert!(
+            words.contains(&normalise(word)),
+            "headline puzzle {id} hides {word}, which isn't in the title \"{title}\""
+        );
+    }
+}
+
+/// Panics unless a sudoku is a square of 4, 6 or 9 rows of digits and `.`s
+/// with no digit given twice in a row, column or box.
+fn check_sudoku(id: &str, body: &str) {
+    let size = body.lines().count();
+    assert!(
+        [4, 6, 9].contains(&size),
+        "sudoku {id} has {size} rows, but only 4, 6 or 9 are supported"
+    );
+    for (y, line) in body.lines().enumerate() {
+        let line = line.trim();
+        assert!(
+            line.chars().count() == size,
+            "sudoku {id} row {} has {} cells instead of {size}",
+            y + 1,
+            line.chars().count()
+        );
+        assert!(
+            line.chars().all(|char| char == '.'
+                || char
+                    .to_digit(10)
+                    .is_some_and(|digit| (1..=size).contains(&(digit as usize)))),
+            "sudoku {id} row {} should only hold `.` and the digits 1 to {size}",
+            y + 1
+        );
+    }
+    let sudoku = Sudoku::from_str(body.to_string().leak());
+    let givens = sudoku
+        .givens
+        .iter()
+        .enumerate()
+        .filter_map(|(index, given)| given.map(|given| (index % size, index / size, given)))
+        .collect_vec();
+    for ((x, y, digit), (other_x, other_y, other_digit)) in givens.iter().tuple_combinations() {
+        let same_box = x / sudoku.region.x == other_x / sudoku.region.x
+            && y / sudoku.region.y == other_y / sudoku.region.y;
+        assert!(
+            digit != other_digit || (x != other_x && y != other_y && !same_box),
+            "sudoku {id} gives {digit} twice, at row {} column {} and row {} column {}",
+            y + 1,
+            x + 1,
+            other_y + 1,
+            other_x + 1
+        );
+    }
+}
+
+/// Panics unless a word search's grid is rectangular and every word can be
+/// found in it.
+fn check_word_search(id: &str, body: &str) {
+    let puzzle = WordSearch::from_str(body.to_string().leak());
+    let size = puzzle.size();
+    assert!(
+        size.x > 0 && puzzle.grid.iter().all(|row| row.len() == size.x),
+        "word search {id} needs a grid with rows of the same length"
+    );
+    for word in &puzzle.words {
+        let length = word.replace(' ', "").chars().count();
+        let end = |start: usize, step: isize, limit: usize| {
+            start
+                .checked_add_signed(step * (length as isize - 1))
+                .filter(|end| *end < limit)
+        };
+        let found = (0..size.y).cartesian_product(0..size.x).any(|(y, x)| {
+            [(1, 0), (0, 1), (1, 1), (1, -1)]
+                .into_iter()
+                .filter_map(|(dx, dy)| {
+                    Some(Vec2 {
+                        x: end(x, dx, size.x)?,
+                        y: end(y, dy, size.y)?,
+                    })
+                })
+                .any(|end| {
+                    WordSearch::line(Vec2 { x, y }, end)
+                        .is_some_and(|cells| puzzle.word_at(&cells) == Some(*word))
+                })
+        });
+        assert!(
+            found,
+            "word search {id} hides {word}, but it isn't in the grid"
+        );
+    }
+}
+
+/// Checks every `src/quizzes/<YYYY-MM-DD>` file and embeds the newest one
+/// published by the day of the build, prefixed with its week.
+fn collect_quiz(article_ids: &[String]) {
+    let today = Local::now().date_naive();
+    let quizzes = read_dir("src/quizzes")
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            let week = entry.file_name().to_string_lossy().to_string();
+            let date = NaiveDate::parse_from_str(&week, "%Y-%m-%d")
+                .unwrap_or_else(|_| panic!("quiz {week} should be named by its date"));
+            let quiz = read_to_string(entry.path()).unwrap().trim().to_string();
+            for question in quiz.split("\n\n") {
+                let mut lines = question.lines();
+                let article = lines.next().unwrap().trim();
+                assert!(
+                    article_ids.iter().any(|id| id == article),
+                    "quiz {week} refers to unknown article {article}"
+                );
+                assert_eq!(
+                    lines.skip(1).filter(|line| line.starts_with('*')).count(),
+                    1,
+                    "each question in quiz {week} needs exactly one answer marked with *"
+                );
+            }
+            (date, week, quiz)
+        })
+        .collect_vec();
+    let latest = quizzes
+        .into_iter()
+        .filter(|(date, _, _)| *date <= today)
+        .max_by_key(|(date, _, _)| *date)
+        .map(|(_, week, quiz)| format!("{week}\n{quiz}"))
+        .unwrap_or_default();
+    File::create(var("OUT_DIR").unwrap() + "/quiz")
+        .unwrap()
+        .write_all(latest.as_bytes())
+        .unwrap();
+}
+
 fn collect_articles() -> Vec<String> {
     let topics = read_dir("src/articles").unwrap();
     let (articles, id): (Vec<_>, Vec<_>) = topics
@@ -161,13 +562,134 @@ fn collect_articles() -> Vec<String> {
     id
 }
 
-fn collect_ads() {
-    let ads: Vec<_> = read_dir("src/images/horizontal-ads")
+/// Lists the sizes, formats and placeholder of every article image in
+/// [`IMAGE_CACHE`]. The cache is only read here; `cargo run --example
+/// cache_images` fills it. Release builds fail while any image is missing from
+/// it, and other builds serve those from their original URL.
+fn collect_images() {
+    let urls = read_dir("src/articles")
+        .unwrap()
+        .flat_map(|topic| read_dir(topic.unwrap().path()).unwrap())
+        .flat_map(|article| {
+            let article = read_to_string(article.unwrap().path()).unwrap();
+            let lead = article.lines().nth(3).unwrap().to_string();
+            let fragments = article
+                .split("\n\n")
+                .filter_map(|fragment| {
+                    let mut lines = fragment.trim().lines();
+                    (lines.next() == Some("image")).then(|| lines.next().unwrap().to_string())
+                })
+                .collect_vec();
+            once(lead).chain(fragments)
+        })
+        .sorted()
+        .dedup();
+    let mut uncached = Vec::new();
+    let images = urls
+        .filter_map(|url| {
+            let stem = format!("{:x}", Sha256::digest(&url))[..16].to_string();
+            let directory = Path::new(IMAGE_CACHE).join(&stem);
+            let Ok(meta) = read_to_string(directory.join("meta")) else {
+                uncached.push(url);
+                return None;
+            };
+            let placeholder = read(directory.join("placeholder.webp")).unwrap_or_else(|_| {
+                panic!("{url} is cached in {directory:?} without a placeholder; delete it and rerun `cargo run --example cache_images`")
+            });
+            Some(format!(
+                "{stem} {} data:image/webp;base64,{} {url}",
+                meta.trim(),
+                STANDARD.encode(placeholder)
+            ))
+        })
+        .join("\n");
+    if !uncached.is_empty() {
+        let message = format!(
+            "{} article images aren't cached; run `cargo run --example cache_images`: {}",
+            uncached.len(),
+            uncached.join(", ")
+        );
+        assert!(var("PROFILE").unwrap() != "release", "{message}");
+        println!("cargo:warning={message}");
+    }
+    File::create(var("OUT_DIR").unwrap() + "/images")
         .unwrap()
-        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
-        .collect();
+        .write_all(images.as_bytes())
+        .unwrap();
+}
+
+/// Checks the `src/ads` manifest against the images on disk. The manifest has a
+/// `[format]` section for each slot format, and each ad in it is an image in
+/// `src/images/<format>-ads` followed by `key value` lines.
+fn collect_ads() {
+    const KEYS: [&str; 7] = [
+        "advertiser",
+        "url",
+        "alt",
+        "weight",
+        "start",
+        "end",
+        "topics",
+    ];
+    let manifest = read_to_string("src/ads").unwrap();
+    let mut format = None;
+    let ads = manifest
+        .trim()
+        .split("\n\n")
+        .filter_map(|ad| {
+            let mut lines = ad.lines().map(str::trim).peekable();
+            if let Some(section) = lines.next_if(|line| line.starts_with('[')) {
+                let section = section.trim_matches(['[', ']']);
+                assert!(AD_FORMATS.contains(&section), "unknown ad format {section}");
+                format = Some(section);
+            }
+            let format = format.expect("ads must be in a [format] section");
+            let image = lines.next()?;
+            assert!(
+                read_dir(format!("src/images/{format}-ads"))
+                    .unwrap()
+                    .any(|entry| entry.unwrap().file_name().to_string_lossy() == image),
+                "{format} ad image {image} is missing"
+            );
+            let mut keys = Vec::new();
+            for line in lines {
+                let (key, value) = line
+                    .split_once(' ')
+                    .unwrap_or_else(|| panic!("ad {image} has no value for {line}"));
+                assert!(KEYS.contains(&key), "ad {image} has unknown key {key}");
+                match key {
+                    "weight" => {
+                        value.parse::<u32>().unwrap();
+                    }
+                    "start" | "end" => {
+                        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap();
+                    }
+                    "url" => assert!(
+                        value.starts_with("https://"),
+                        "ad {image} links to {value}, which isn't https"
+                    ),
+                    _ => {}
+                }
+                keys.push(key);
+            }
+            for key in ["advertiser", "alt"] {
+                assert!(keys.contains(&key), "ad {image} needs {key}");
+            }
+            Some(
+                once(format!("{format} {image}"))
+                    .chain(
+                        ad.lines()
+                            .map(str::trim)
+                            .skip_while(|line| *line != image)
+                            .skip(1)
+                            .map(str::to_string),
+                    )
+                    .join("\n"),
+            )
+        })
+        .collect_vec();
     File::create(var("OUT_DIR").unwrap() + "/ads")
         .unwrap()
-        .write_all(ads.join("\n").as_bytes())
+        .write_all(ads.join("\n\n").as_bytes())
         .unwrap();
 }
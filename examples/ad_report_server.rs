@@ -0,0 +1,33 @@
+//! A stand-in for the ad reporting endpoint that prints each batch of events
+//! it receives and tallies them per ad.
+//!
+//! ```sh
+//! cargo run --example ad_report_server
+//! AD_REPORT_ENDPOINT=http://localhost:8787 trunk serve
+//! ```
+
+use std::collections::BTreeMap;
+use std::env::args;
+
+mod server;
+
+fn main() {
+    let address = args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8787".to_string());
+    let mut totals = BTreeMap::<(String, String), usize>::new();
+    server::serve(&address, |body| {
+        println!("Received {} events:", body.lines().count());
+        for line in body.lines() {
+            println!("  {line}");
+            if let [_, event, id] = line.split(' ').collect::<Vec<_>>()[..] {
+                *totals
+                    .entry((id.to_string(), event.to_string()))
+                    .or_default() += 1;
+            }
+        }
+        for ((id, event), count) in &totals {
+            println!("{id}: {count} {event}s");
+        }
+    });
+}
@@ -0,0 +1,62 @@
+//! A stand-in for the self-hosted analytics endpoint that prints each event it
+//! receives, along with page views so far and how each article is being read.
+//!
+//! ```sh
+//! cargo run --example analytics_collector
+//! ANALYTICS_ENDPOINT=http://localhost:8788 trunk serve
+//! ```
+
+use std::collections::BTreeMap;
+use std::env::args;
+
+mod server;
+
+#[derive(Default)]
+struct Reads {
+    count: u64,
+    seconds: u64,
+    depth: u64,
+    /// Reads that lasted at least half the expected reading time.
+    finished: u64,
+}
+
+fn main() {
+    let address = args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:8788".to_string());
+    let mut views = BTreeMap::<String, u64>::new();
+    let mut reads = BTreeMap::<String, Reads>::new();
+    server::serve(&address, |body| {
+        println!("{body}");
+        match body.split(' ').collect::<Vec<_>>()[..] {
+            ["view", path] => *views.entry(path.to_string()).or_default() += 1,
+            ["read", id, seconds, depth, minutes] => {
+                let (Ok(seconds), Ok(depth), Ok(minutes)) = (
+                    seconds.parse::<u64>(),
+                    depth.parse::<u64>(),
+                    minutes.parse::<u64>(),
+                ) else {
+                    return;
+                };
+                let reads = reads.entry(id.to_string()).or_default();
+                reads.count += 1;
+                reads.seconds += seconds;
+                reads.depth += depth;
+                reads.finished += u64::from(seconds * 2 >= minutes * 60);
+            }
+            _ => println!("Unknown event"),
+        }
+        for (path, count) in &views {
+            println!("  {path}: {count} views");
+        }
+        for (id, reads) in &reads {
+            println!(
+                "  {id}: {} reads, {}s and {}% down on average, {} finished",
+                reads.count,
+                reads.seconds / reads.count,
+                reads.depth / reads.count,
+                reads.finished,
+            );
+        }
+    });
+}
@@ -0,0 +1,154 @@
+//! Resizes every article image into `src/images/cache`, downloading hotlinked
+//! ones first with `curl`. The build only reads the cache, so run this and
+//! commit the results whenever an article adds an image; release builds fail
+//! while any image is missing. Images already in the cache are left alone;
+//! delete a directory to redo it.
+//!
+//! ```sh
+//! cargo run --example cache_images
+//! ```
+
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir_all, remove_file, write, File};
+use std::io::BufWriter;
+use std::iter::once;
+use std::path::Path;
+use std::process::{exit, Command};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType as PngFilter, PngEncoder};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader};
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+use webp::Encoder;
+
+/// Served from `/images/cache`, one directory per image named after the first
+/// 16 hex digits of the SHA-256 of its URL.
+const IMAGE_CACHE: &str = "src/images/cache";
+const IMAGE_WIDTHS: [u32; 5] = [320, 640, 960, 1280, 1920];
+
+fn main() {
+    let urls = read_dir("src/articles")
+        .unwrap()
+        .flat_map(|topic| read_dir(topic.unwrap().path()).unwrap())
+        .flat_map(|article| {
+            let article = read_to_string(article.unwrap().path()).unwrap();
+            let lead = article.lines().nth(3).unwrap().to_string();
+            let fragments = article
+                .split("\n\n")
+                .filter_map(|fragment| {
+                    let mut lines = fragment.trim().lines();
+                    (lines.next() == Some("image")).then(|| lines.next().unwrap().to_string())
+                })
+                .collect_vec();
+            once(lead).chain(fragments)
+        })
+        .sorted()
+        .dedup();
+    let mut failed = false;
+    for url in urls {
+        let stem = format!("{:x}", Sha256::digest(&url))[..16].to_string();
+        let directory = Path::new(IMAGE_CACHE).join(&stem);
+        if directory.join("meta").exists() {
+            continue;
+        }
+        match ingest(&url, &directory) {
+            Ok(meta) => println!("{stem} {meta} {url}"),
+            Err(error) => {
+                eprintln!("couldn't cache {url}: {error}");
+                _ = remove_dir_all(&directory);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+/// Fetches an image into `directory` and writes each width as WebP and as PNG
+/// or JPEG, depending on whether it has any transparency, along with a tiny WebP placeholder, returning the
+/// `width height extension widths formats` line saved as its `meta`.
+fn ingest(url: &str, directory: &Path) -> Result<String, String> {
+    create_dir_all(directory).unwrap();
+    let downloaded = directory.join("source");
+    let path = match url.strip_prefix('/') {
+        Some(path) => Path::new("src").join(path),
+        None => {
+            let output = Command::new("curl")
+                .args([
+                    "--silent",
+                    "--show-error",
+                    "--fail",
+                    "--location",
+                    "--max-time",
+                    "60",
+                ])
+                .arg("--output")
+                .arg(&downloaded)
+                .arg(url)
+                .output()
+                .map_err(|error| format!("curl: {error}"))?;
+            if !output.status.success() {
+                return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+            }
+            downloaded.clone()
+        }
+    };
+    let reader = ImageReader::open(&path)
+        .and_then(ImageReader::with_guessed_format)
+        .map_err(|error| error.to_string())?;
+    let source = reader.decode().map_err(|error| error.to_string())?;
+    // PNG is only worth its size for images that need transparency.
+    let transparent =
+        source.color().has_alpha() && source.to_rgba8().pixels().any(|pixel| pixel.0[3] < u8::MAX);
+    let extension = if transparent { "png" } else { "jpg" };
+    _ = remove_file(downloaded);
+    let (width, height) = (source.width(), source.height());
+    let widths = IMAGE_WIDTHS
+        .into_iter()
+        .filter(|size| *size < width)
+        .chain(once(width.min(*IMAGE_WIDTHS.last().unwrap())))
+        .dedup()
+        .collect_vec();
+    for size in &widths {
+        let resized = source.resize(*size, u32::MAX, FilterType::Lanczos3);
+        write(
+            directory.join(format!("{size}.webp")),
+            encode_webp(&resized, 80.0),
+        )
+        .unwrap();
+        let path = directory.join(format!("{size}.{extension}"));
+        let file = BufWriter::new(File::create(path).unwrap());
+        if extension == "png" {
+            resized
+                .write_with_encoder(PngEncoder::new_with_quality(
+                    file,
+                    CompressionType::Best,
+                    PngFilter::Adaptive,
+                ))
+                .map_err(|error| error.to_string())?;
+        } else {
+            resized
+                .to_rgb8()
+                .write_with_encoder(JpegEncoder::new_with_quality(file, 80))
+                .map_err(|error| error.to_string())?;
+        }
+    }
+    let tiny = source.resize(16, u32::MAX, FilterType::Triangle);
+    write(directory.join("placeholder.webp"), encode_webp(&tiny, 50.0)).unwrap();
+    let meta = format!(
+        "{width} {height} {extension} {} webp,{extension}",
+        widths.iter().join(","),
+    );
+    write(directory.join("meta"), &meta).unwrap();
+    Ok(meta)
+}
+
+fn encode_webp(image: &DynamicImage, quality: f32) -> Vec<u8> {
+    let image = DynamicImage::ImageRgba8(image.to_rgba8());
+    Encoder::from_image(&image)
+        .unwrap()
+        .encode(quality)
+        .to_vec()
+}
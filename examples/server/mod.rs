@@ -0,0 +1,42 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+
+/// Accepts requests on `address` forever, passing the body of each `POST` to
+/// `handle` and answering everything with an empty response any page can
+/// read.
+pub fn serve(address: &str, mut handle: impl FnMut(&str)) {
+    let listener = TcpListener::bind(address).unwrap();
+    println!("Listening on http://{address}");
+    for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut reader = BufReader::new(&stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        if request_line.starts_with("POST") {
+            handle(&String::from_utf8_lossy(&body));
+        }
+        stream
+            .write_all(
+                b"HTTP/1.1 204 No Content\r\n\
+                Access-Control-Allow-Origin: *\r\n\
+                Access-Control-Allow-Methods: POST\r\n\
+                Connection: close\r\n\r\n",
+            )
+            .unwrap();
+    }
+}
@@ -1,9 +1,235 @@
+use chrono::{Local, NaiveDate};
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use leptos::web_sys::Storage;
+use leptos::window;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use strum::{Display, EnumString, VariantArray};
+
+use crate::storage::storage;
+
+pub mod report;
+
+/// Ads the reader has hidden, kept in `localStorage` as `id reason` lines.
+const HIDDEN: &str = "hidden-ads";
+/// How often each ad has been shown this session, as `id count` lines. This
+/// only caps views on this device, so it doesn't need the reader's consent.
+const VIEWS: &str = "ad-views";
+/// Set for the session once the reader closes the sticky banner.
+const CLOSED: &str = "ad-banner-closed";
+/// How many times an ad is shown per session before others take its place.
+const FREQUENCY_CAP: usize = 3;
+
+/// How much more often an ad targeted at the current topic is shown than an
+/// untargeted one of the same weight.
+const RELEVANCE: u32 = 4;
+
+/// The shape of an ad slot, each with its own `src/images/<format>-ads`
+/// directory and manifest section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Format {
+    /// The sticky banner along the bottom of an article.
+    Horizontal,
+    /// A tall banner in the sidebar beside an article.
+    Vertical,
+    Square,
+    /// A wide banner between an article's paragraphs.
+    Inline,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ad {
+    pub format: Format,
+    pub image: &'static str,
+    pub advertiser: &'static str,
+    /// Where a click goes, or nowhere if the ad is only an image.
+    pub url: Option<&'static str>,
+    pub alt: &'static str,
+    pub weight: u32,
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+    /// The article topics the ad is shown on, or all of them if empty.
+    pub topics: Vec<&'static str>,
+}
+
+impl Ad {
+    /// Parses a `format image` line followed by `key value` lines.
+    fn from_str(s: &'static str) -> Self {
+        let mut lines = s.lines();
+        let (format, image) = lines.next().unwrap().split_once(' ').unwrap();
+        let mut ad = Self {
+            format: format.parse().unwrap(),
+            image,
+            advertiser: "",
+            url: None,
+            alt: "",
+            weight: 1,
+            start: None,
+            end: None,
+            topics: Vec::new(),
+        };
+        for line in lines {
+            let (key, value) = line.split_once(' ').unwrap();
+            match key {
+                "advertiser" => ad.advertiser = value,
+                "url" => ad.url = Some(value),
+                "alt" => ad.alt = value,
+                "weight" => ad.weight = value.parse().unwrap(),
+                "start" => ad.start = Some(value.parse().unwrap()),
+                "end" => ad.end = Some(value.parse().unwrap()),
+                "topics" => ad.topics = value.split(',').map(str::trim).collect(),
+                _ => unreachable!("unknown ad key {key}"),
+            }
+        }
+        ad
+    }
+
+    /// Identifies the ad in storage, as the same image may run in several
+    /// formats.
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.format, self.image)
+    }
+
+    pub fn src(&self) -> String {
+        format!("/images/{}-ads/{}", self.format, self.image)
+    }
+
+    pub fn is_running(&self, today: NaiveDate) -> bool {
+        self.start.is_none_or(|start| start <= today) && self.end.is_none_or(|end| today <= end)
+    }
+
+    /// The ad's weight on a page about `topic`, or 0 if it's targeted elsewhere.
+    fn weight_for(&self, topic: Option<&str>) -> u32 {
+        if self.topics.is_empty() {
+            self.weight
+        } else if topic.is_some_and(|topic| self.topics.contains(&topic)) {
+            self.weight * RELEVANCE
+        } else {
+            0
+        }
+    }
+}
+
+/// Why the reader asked not to see an ad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Reason {
+    Irrelevant,
+    Repetitive,
+    Inappropriate,
+    Misleading,
+}
+
+impl Reason {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Irrelevant => "Not relevant to me",
+            Self::Repetitive => "Seen it too many times",
+            Self::Inappropriate => "Inappropriate or offensive",
+            Self::Misleading => "Misleading or a scam",
+        }
+    }
+}
+
+fn session() -> Option<Storage> {
+    window().session_storage().ok().flatten()
+}
+
+/// Parses `id value` lines from a storage key, as written by [`set_lines`].
+fn lines(storage: Option<Storage>, key: &str) -> Vec<(String, String)> {
+    storage
+        .and_then(|storage| storage.get_item(key).ok().flatten())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (id, value) = line.split_once(' ')?;
+            Some((id.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn set_lines(storage: Option<Storage>, key: &str, lines: &[(String, String)]) {
+    if let Some(storage) = storage {
+        storage
+            .set_item(
+                key,
+                &lines
+                    .iter()
+                    .map(|(id, value)| format!("{id} {value}"))
+                    .join("\n"),
+            )
+            .unwrap();
+    }
+}
+
+/// The ads the reader has hidden, with their reasons.
+pub fn hidden() -> Vec<(String, Reason)> {
+    lines(storage(), HIDDEN)
+        .into_iter()
+        .filter_map(|(id, reason)| Some((id, reason.parse().ok()?)))
+        .collect()
+}
+
+/// Stops an ad being shown again, remembering why.
+pub fn hide(ad: &Ad, reason: Reason) {
+    let mut hidden = lines(storage(), HIDDEN);
+    hidden.retain(|(id, _)| *id != ad.id());
+    hidden.push((ad.id(), reason.to_string()));
+    set_lines(storage(), HIDDEN, &hidden);
+}
+
+fn views(ad: &Ad) -> usize {
+    lines(session(), VIEWS)
+        .into_iter()
+        .find(|(id, _)| *id == ad.id())
+        .and_then(|(_, count)| count.parse().ok())
+        .unwrap_or_default()
+}
+
+fn record_view(ad: &Ad) {
+    let mut views = lines(session(), VIEWS);
+    match views.iter_mut().find(|(id, _)| *id == ad.id()) {
+        Some((_, count)) => *count = (count.parse::<usize>().unwrap_or_default() + 1).to_string(),
+        None => views.push((ad.id(), 1.to_string())),
+    }
+    set_lines(session(), VIEWS, &views);
+}
+
+pub fn is_banner_closed() -> bool {
+    session().is_some_and(|session| session.get_item(CLOSED).ok().flatten().is_some())
+}
+
+/// Collapses the sticky banner for the rest of the session.
+pub fn close_banner() {
+    if let Some(session) = session() {
+        session.set_item(CLOSED, "true").unwrap();
+    }
+}
+
+/// Picks a running ad for a slot at random by weight, favouring ads targeted
+/// at `topic` and skipping ads the reader has hidden or seen too often this
+/// session. The chosen ad counts as seen.
+pub fn choose(format: Format, topic: Option<&str>) -> Option<&'static Ad> {
+    let today = Local::now().date_naive();
+    let hidden = hidden();
+    let ad = ADS
+        .iter()
+        .filter(|ad| ad.format == format && ad.is_running(today) && ad.weight_for(topic) > 0)
+        .filter(|ad| hidden.iter().all(|(id, _)| *id != ad.id()))
+        .filter(|ad| views(ad) < FREQUENCY_CAP)
+        .collect_vec()
+        .choose_weighted(&mut thread_rng(), |ad| ad.weight_for(topic))
+        .ok()
+        .copied()?;
+    record_view(ad);
+    Some(ad)
+}
 
 lazy_static! {
-    pub static ref ADS: &'static [&'static str] = {
+    pub static ref ADS: &'static [Ad] = {
         let data = include_str!(concat!(env!("OUT_DIR"), "/ads"));
-        data.lines().collect_vec().leak()
+        data.split("\n\n").map(Ad::from_str).collect_vec().leak()
     };
 }
@@ -0,0 +1,146 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::Utc;
+use itertools::Itertools;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use leptos::web_sys::js_sys::Array;
+use leptos::web_sys::{
+    Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit,
+    RequestCredentials, RequestInit, Response,
+};
+use leptos::{set_interval, spawn_local, window};
+use strum::Display;
+use wasm_bindgen_futures::JsFuture;
+
+use super::Ad;
+use crate::consent::{self, Category};
+use crate::storage::storage;
+
+/// Where batches of events are posted, set when building the site. Without
+/// one, or without the reader's consent to personalised ads, nothing is
+/// recorded or sent.
+const ENDPOINT: Option<&str> = option_env!("AD_REPORT_ENDPOINT");
+/// Events waiting to be sent, kept in `localStorage` as `time event id` lines
+/// so they survive failed requests and closed tabs.
+const QUEUE: &str = "ad-report-queue";
+const INTERVAL: Duration = Duration::from_secs(15);
+/// Queued events past which a batch is sent straight away.
+const BATCH_SIZE: usize = 20;
+/// Queued events past which the oldest are dropped, e.g. if the endpoint has
+/// been down for a long time.
+const MAX_QUEUED: usize = 1000;
+/// How much of an ad must be on screen to count as an impression.
+const VISIBLE: f64 = 0.5;
+
+static SENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Event {
+    Impression,
+    Click,
+}
+
+fn enabled() -> bool {
+    ENDPOINT.is_some() && consent::granted(Category::PersonalisedAds)
+}
+
+fn queued() -> Vec<String> {
+    storage()
+        .and_then(|storage| storage.get_item(QUEUE).ok().flatten())
+        .unwrap_or_default()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+fn set_queued(events: &[String]) {
+    if let Some(storage) = storage() {
+        storage.set_item(QUEUE, &events.join("\n")).unwrap();
+    }
+}
+
+/// Queues an event for an ad, sending the queue if it's grown large enough.
+pub fn record(event: Event, ad: &Ad) {
+    if !enabled() {
+        return;
+    }
+    let mut events = queued();
+    events.push(format!(
+        "{} {event} {}",
+        Utc::now().timestamp_millis(),
+        ad.id()
+    ));
+    let overflow = events.len().saturating_sub(MAX_QUEUED);
+    set_queued(&events[overflow..]);
+    if events.len() >= BATCH_SIZE {
+        spawn_local(send());
+    }
+}
+
+/// Records an impression once at least half of `element` has been on screen.
+pub fn observe(element: &Element, ad: &'static Ad) {
+    if !enabled() {
+        return;
+    }
+    let callback = Closure::<dyn FnMut(Array, IntersectionObserver)>::new(
+        move |entries: Array, observer: IntersectionObserver| {
+            let seen = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<IntersectionObserverEntry>()
+                    .intersection_ratio()
+                    >= VISIBLE
+            });
+            if seen {
+                record(Event::Impression, ad);
+                observer.disconnect();
+            }
+        },
+    );
+    let init = IntersectionObserverInit::new();
+    init.set_threshold(&JsValue::from_f64(VISIBLE));
+    let observer =
+        IntersectionObserver::new_with_options(callback.into_js_value().unchecked_ref(), &init)
+            .unwrap();
+    observer.observe(element);
+}
+
+/// Posts queued events to the endpoint, keeping them for the next attempt if
+/// the request fails, or drops them if the reader has withdrawn consent.
+async fn send() {
+    let Some(endpoint) = ENDPOINT else {
+        return;
+    };
+    if !enabled() {
+        set_queued(&[]);
+        return;
+    }
+    let events = queued();
+    if events.is_empty() || SENDING.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_credentials(RequestCredentials::Omit);
+    init.set_body(&JsValue::from_str(&events.join("\n")));
+    let request = window().fetch_with_str_and_init(endpoint, &init);
+    let sent = JsFuture::from(request)
+        .await
+        .is_ok_and(|response| response.unchecked_into::<Response>().ok());
+    if sent {
+        // Events recorded while the request was in flight are still queued.
+        set_queued(&queued().into_iter().skip(events.len()).collect_vec());
+    }
+    SENDING.store(false, Ordering::Relaxed);
+}
+
+/// Sends queued events every so often, including any left from earlier visits.
+pub fn start() {
+    if ENDPOINT.is_none() {
+        return;
+    }
+    spawn_local(send());
+    set_interval(|| spawn_local(send()), INTERVAL);
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Local};
+use leptos::wasm_bindgen::JsValue;
+use leptos::web_sys::js_sys::Reflect;
+use leptos::web_sys::{RequestCredentials, RequestInit};
+use leptos::{document, spawn_local, window};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::article::{Article, ARTICLES};
+use crate::consent::{self, Category};
+
+/// Where page views are posted, set when building the site. Without one,
+/// nothing is sent.
+const ENDPOINT: Option<&str> = option_env!("ANALYTICS_ENDPOINT");
+/// Whether the reader has asked not to be tracked, through Do Not Track or
+/// Global Privacy Control.
+pub fn do_not_track() -> bool {
+    let navigator = window().navigator();
+    navigator.do_not_track() == "1"
+        || Reflect::get(&navigator, &JsValue::from_str("globalPrivacyControl"))
+            .is_ok_and(|gpc| gpc.is_truthy())
+}
+
+fn enabled() -> bool {
+    ENDPOINT.is_some() && !do_not_track() && consent::granted(Category::Analytics)
+}
+
+/// Posts an event without cookies or any way of telling readers apart, and
+/// without waiting for the page to stay open.
+fn send(event: String) {
+    let Some(endpoint) = ENDPOINT.filter(|_| enabled()) else {
+        return;
+    };
+    let init = RequestInit::new();
+    init.set_method("POST");
+    init.set_credentials(RequestCredentials::Omit);
+    init.set_body(&JsValue::from_str(&event));
+    Reflect::set(&init, &JsValue::from_str("keepalive"), &JsValue::TRUE).unwrap();
+    let request = window().fetch_with_str_and_init(endpoint, &init);
+    spawn_local(async {
+        _ = JsFuture::from(request).await;
+    });
+}
+
+/// How far down the page the reader can see, from 0 to 1.
+fn scroll_depth() -> f64 {
+    let height = f64::from(document().document_element().unwrap().scroll_height());
+    let bottom = window().scroll_y().unwrap() + window().inner_height().unwrap().as_f64().unwrap();
+    (bottom / height).min(1.0)
+}
+
+/// A reader's time on a page, reported as a view when it starts and, for
+/// articles, with how long and how far it was read when it ends.
+pub struct Visit {
+    article: Option<&'static Article>,
+    started: DateTime<Local>,
+    depth: f64,
+}
+
+impl Visit {
+    pub fn start(path: &str) -> Self {
+        send(format!("view {path}"));
+        let id = path.strip_prefix("/articles/");
+        Self {
+            article: ARTICLES.iter().find(|article| Some(article.id) == id),
+            started: Local::now(),
+            depth: scroll_depth(),
+        }
+    }
+
+    pub fn scrolled(&mut self) {
+        self.depth = self.depth.max(scroll_depth());
+    }
+
+    /// Reports an article's `read id seconds depth reading-time` line, with
+    /// the depth as a percentage and the expected reading time in minutes.
+    pub fn end(self) {
+        if let Some(article) = self.article {
+            send(format!(
+                "read {} {} {:.0} {}",
+                article.id,
+                (Local::now() - self.started).num_seconds(),
+                self.depth * 100.0,
+                article.reading_time(),
+            ));
+        }
+    }
+}
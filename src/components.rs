@@ -1,36 +1,56 @@
 use crate::crossword::{Crossword, Direction, Vec2, Word};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use itertools::Itertools;
-use leptos::ev::{keydown, scroll, KeyboardEvent, MouseEvent};
+use leptos::ev::{pagehide, scroll, KeyboardEvent, MouseEvent};
 use leptos::leptos_dom::helpers::location;
+use leptos::web_sys::js_sys::encode_uri_component;
 use leptos::web_sys::HtmlButtonElement;
 use leptos_meta::{provide_meta_context, Meta};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::iter::from_fn;
 use std::iter::once;
-use std::ops::{Index, Neg, Not};
+use std::ops::{Neg, Not};
 use std::str::FromStr;
 
-use crate::ad::ADS;
+use crate::ad::report::{self, Event};
+use crate::ad::{self, Ad, Format, Reason};
+use crate::analytics::{self, Visit};
 use crate::article::{Article, ARTICLES};
 use crate::article::{Fragment, Image};
-use crate::crossword::CROSSWORDS;
+use crate::consent::{self, Category};
+use crate::crossword::{self, released, CROSSWORDS};
+use crate::offline;
+use crate::picture::{self, Variants};
+use crate::puzzle::{find, Headline, Kind, Puzzle, Sudoku, WordSearch, PUZZLES};
+use crate::quiz::{self, QUIZ};
+use crate::speech;
+use crate::stats::{histogram, record, solves, streaks, Solve};
+use crate::theme::{self, Theme};
+use crate::typography::{Face, Settings, Size, Spacing};
 use chrono::Local;
 
+use leptos::html::{Img, Input};
 use leptos::{
-    component, create_memo, create_signal, document, event_target, view, window_event_listener,
-    Callback, Children, CollectView, IntoView, Params, SignalGet, SignalWith,
+    component, create_effect, create_memo, create_node_ref, create_signal, document, event_target,
+    event_target_checked, event_target_value, on_cleanup, store_value, untrack, view, window,
+    window_event_listener, Callback, Children, CollectView, IntoView, NodeRef, Params, ReadSignal,
+    Signal, SignalGet, SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked,
+    WriteSignal,
 };
-use leptos_router::A;
-use leptos_router::{use_params, Route, Router, Routes};
+use leptos_router::{use_location, use_params, Route, Router, Routes};
 use leptos_router::{use_params_map, Params};
+use leptos_router::{Redirect, A};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
+use strum::VariantArray;
 
 #[component]
 pub fn App() -> impl IntoView {
     provide_meta_context();
     view! {
         <Router>
+            <Analytics />
             <div class="flex flex-col h-full">
                 <Routes>
                     <Route
@@ -69,6 +89,91 @@ pub fn App() -> impl IntoView {
                             }
                         }
                     />
+                    <Route path="/crosswords/:id/print" view=CrosswordPrint />
+                    <Route
+                        path="/crosswords"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <CrosswordArchive />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
+                    <Route
+                        path="/crosswords/stats"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <CrosswordStats />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
+                    <Route
+                        path="/crosswords/today"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <CrosswordToday />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
+                    <Route
+                        path="/puzzles"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <Puzzles />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
+                    <Route
+                        path="/puzzles/:kind/:id"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <Puzzle />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
+                    <Route
+                        path="/quiz"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <Quiz />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
+                    <Route
+                        path="/privacy"
+                        view=|| {
+                            view! {
+                                <Header />
+                                <PageContainer>
+                                    <Privacy />
+                                </PageContainer>
+                                <Footer />
+                            }
+                        }
+                    />
                     <Route
                         path="/*"
                         view=|| {
@@ -82,6 +187,7 @@ pub fn App() -> impl IntoView {
                 </Routes>
 
             </div>
+            <ConsentBanner />
         </Router>
     }
 }
@@ -89,16 +195,18 @@ pub fn App() -> impl IntoView {
 #[component]
 pub fn Header(#[prop(optional)] small: bool) -> impl IntoView {
     view! {
-        <header class="relative p-4 text-white bg-black">
+        <header class="relative p-4 text-masthead-ink bg-masthead">
             <div class="inset-0 items-center justify-between hidden pointer-events-none sm:p-4 sm:absolute sm:flex">
                 <div>{Local::now().format("%B %-d, %Y").to_string()}</div>
-                <A
-                    href="https://angusmason.github.io/theaccountgame"
-                    target="_blank"
-                    class="pointer-events-auto"
-                >
-                    "Sign Up"
-                </A>
+                <div class="flex items-center gap-4 pointer-events-auto">
+                    <ThemePicker />
+                    <A href="https://angusmason.github.io/theaccountgame" target="_blank">
+                        "Sign Up"
+                    </A>
+                </div>
+            </div>
+            <div class="absolute top-2 right-2 sm:hidden">
+                <ThemePicker />
             </div>
             <a
                 class="w-full text-center"
@@ -131,6 +239,99 @@ pub fn Header(#[prop(optional)] small: bool) -> impl IntoView {
     }
 }
 
+#[component]
+pub fn ThemePicker() -> impl IntoView {
+    let saved = theme::saved();
+    view! {
+        <select
+            class="text-sm bg-transparent cursor-pointer"
+            aria-label="Theme"
+            on:change=move |event| theme::set(Theme::from_str(&event_target_value(&event)).unwrap())
+        >
+            {Theme::VARIANTS
+                .iter()
+                .map(|theme| {
+                    view! {
+                        <option
+                            value=theme.to_string()
+                            selected=*theme == saved
+                            class="bg-paper text-ink"
+                        >
+                            {theme.name()}
+                        </option>
+                    }
+                })
+                .collect_view()}
+        </select>
+    }
+}
+
+/// Reports each page the reader visits, if they've agreed to analytics.
+#[component]
+pub fn Analytics() -> impl IntoView {
+    let pathname = use_location().pathname;
+    let visit = store_value(None::<Visit>);
+    let end = move || {
+        if let Some(visit) = visit.try_update_value(Option::take).flatten() {
+            visit.end();
+        }
+    };
+    create_effect(move |_| {
+        let path = pathname();
+        end();
+        visit.set_value(Some(Visit::start(&path)));
+    });
+    window_event_listener(scroll, move |_| {
+        visit.update_value(|visit| {
+            if let Some(visit) = visit {
+                visit.scrolled();
+            }
+        });
+    });
+    window_event_listener(pagehide, move |_| end());
+}
+
+/// Asks readers about tracking until they've made a choice, here or on the
+/// privacy page.
+#[component]
+pub fn ConsentBanner() -> impl IntoView {
+    let pathname = use_location().pathname;
+    let (chosen, set_chosen) = create_signal(false);
+    let choose = move |granted| {
+        consent::set_all(granted);
+        set_chosen(true);
+    };
+    view! {
+        <div
+            class="fixed inset-x-0 bottom-0 z-20 flex flex-col items-center gap-2 p-4 text-sm bg-paper border-t shadow sm:flex-row sm:justify-center"
+            class:hidden=move || chosen() || pathname() == "/privacy" || consent::asked()
+            role="dialog"
+            aria-label="Privacy choices"
+        >
+            <p class="max-w-xl">
+                "We'd like to count which stories are read and tell advertisers how their ads do. We never use cookies or anything that identifies you."
+            </p>
+            <div class="flex items-center gap-2">
+                <button
+                    class="px-3 py-1 rounded-sm shadow text-paper bg-accent"
+                    on:click=move |_| choose(true)
+                >
+                    "Accept all"
+                </button>
+                <button
+                    class="px-3 py-1 bg-paper border rounded-sm shadow"
+                    on:click=move |_| choose(false)
+                >
+                    "Reject all"
+                </button>
+                <A href="/privacy" class="px-3 py-1 underline">
+                    "Choose"
+                </A>
+            </div>
+        </div>
+    }
+}
+
 #[component]
 pub fn PageContainer(children: Children) -> impl IntoView {
     view! {
@@ -154,7 +355,7 @@ pub fn ArticlePreviews() -> impl IntoView {
         />
         <div class="flex flex-col items-center w-full gap-2 p-4 md:p-0">
             <div
-                class="sticky top-0 z-50 justify-center hidden w-full p-2 transition bg-white md:flex"
+                class="sticky top-0 z-50 justify-center hidden w-full p-2 transition bg-paper md:flex"
                 class:shadow=stuck
             >
                 <div class="flex *:px-3 divide-x font-noto justify-center py-2">
@@ -170,8 +371,8 @@ pub fn ArticlePreviews() -> impl IntoView {
                                         class=filter
                                             .get()
                                             .as_ref()
-                                            .map_or(false, |filter| topic == *filter)
-                                            .then_some("text-blue-800")
+                                            .is_some_and(|filter| topic == *filter)
+                                            .then_some("text-accent")
                                         on:click=move |_| set_filter(Some(topic))
                                     >
                                         {topic}
@@ -232,16 +433,17 @@ pub fn ArticlePreviews() -> impl IntoView {
                                                 <div class="flex flex-col gap-2 md:hidden">
                                                     {all.clone()}
                                                 </div>
-                                                <div class="hidden md:flex divide-x py-4 divide-gray-300 first:*:pr-4 last:*:pl-4">
+                                                <div class="hidden md:flex divide-x py-4 divide-rule first:*:pr-4 last:*:pl-4">
                                                     <div class="flex flex-col w-2/3 gap-4">
                                                         {next!(hero)} <div class="flex gap-4 *:basis-0 *:grow">
                                                             <div>{next!()}</div>
                                                             <div>{next!()}</div>
                                                         </div>
                                                     </div>
-                                                    <div class="flex flex-col divide-y divide-gray-300 *:py-4 first:*:pt-0 last:*:pb-0 w-1/3">
+                                                    <div class="flex flex-col divide-y divide-rule *:py-4 first:*:pt-0 last:*:pb-0 w-1/3">
                                                         {next!()}
                                                         {from_fn(|| next!(without_image)).take(3).collect_view()}
+                                                        <AdSlot format=Format::Square />
                                                     </div>
                                                 </div>
                                             }
@@ -264,7 +466,7 @@ pub fn ArticlePreviews() -> impl IntoView {
                                                 let main = articles.next().unwrap();
                                                 view! {
                                                     <div class="flex flex-col gap-2 md:hidden">{all}</div>
-                                                    <div class="hidden grid-cols-3 first:*:pr-4 last:*:pl-4 md:grid divide-x pb-4 divide-gray-300">
+                                                    <div class="hidden grid-cols-3 first:*:pr-4 last:*:pl-4 md:grid divide-x pb-4 divide-rule">
                                                         <div class="grid grid-cols-8 col-span-2 gap-4">
                                                             <div class="flex flex-col col-span-3 gap-4">
                                                                 <ArticlePreview
@@ -279,9 +481,10 @@ pub fn ArticlePreviews() -> impl IntoView {
                                                                 class="flex flex-col col-span-5"
                                                                 href=format!("/articles/{}", main.id)
                                                             >
-                                                                <img
-                                                                    src=main.image.url
+                                                                <Picture
+                                                                    url=main.image.url
                                                                     alt=main.image.caption
+                                                                    sizes="(min-width: 768px) 42vw, 100vw"
                                                                     class="object-cover aspect-[3/2]"
                                                                 />
                                                             </A>
@@ -392,9 +595,10 @@ pub fn ArticlePreview(
                 .image
                 .then_some(
                     view! {
-                        <img
-                            src=article.image.url
+                        <Picture
+                            url=article.image.url
                             alt=article.image.caption
+                            sizes="(min-width: 768px) 33vw, 100vw"
                             class="object-cover w-full aspect-[3/2]"
                         />
                     },
@@ -404,9 +608,7 @@ pub fn ArticlePreview(
                     .category
                     .then_some(
                         view! {
-                            <div class="font-light text-blue-800">
-                                {article.topic.to_uppercase()}
-                            </div>
+                            <div class="font-light text-accent">{article.topic.to_uppercase()}</div>
                         },
                     )} <Heading>
                     <article class=if layout.size == ArticleSize::Hero {
@@ -437,6 +639,15 @@ pub fn ArticlePreview(
 
 #[component]
 pub fn Article() -> impl IntoView {
+    /// Text paragraphs between in-article ads.
+    const AD_EVERY: usize = 3;
+    /// Images are inset from the `max-w-2xl` column on wider screens.
+    const ARTICLE_IMAGE_SIZES: &str = "(min-width: 640px) 544px, 100vw";
+    const DROP_CAP: &str = "[&>div:first-child>p]:first-letter:text-[3.2em]
+        [&>div:first-child>p]:first-letter:leading-none
+        [&>div:first-child>p]:first-letter:font-bold
+        [&>div:first-child>p]:first-letter:float-left
+        [&>div:first-child>p]:first-letter:pr-2";
     #[derive(Params, PartialEq)]
     struct ArticleParams {
         id: String,
@@ -449,6 +660,10 @@ pub fn Article() -> impl IntoView {
                 .unwrap()
         })
     };
+    let (settings, set_settings) = create_signal(Settings::load());
+    let (reading, set_reading) = create_signal(None::<usize>);
+    create_effect(move |_| offline::keep(article()));
+    create_effect(move |_| settings().save());
     view! {
         <Meta name="description" content=move || article().blurb />
         <div class="w-full max-w-2xl p-4 shrink-0">
@@ -460,51 +675,84 @@ pub fn Article() -> impl IntoView {
                             {move || article().blurb}
                         </div>
                     </Caption>
-                    <div class="flex gap-1 text-base font-light">
-                        <div class="text-blue-800">{move || article().topic.to_uppercase()}</div>
+                    <div class="flex items-center gap-1 text-base font-light">
+                        <div class="text-accent">{move || article().topic.to_uppercase()}</div>
                         "\u{b7} "
                         {move || article().reading_time()}
                         " min read"
+                        <div class="ml-auto">
+                            <ReaderSettings settings=settings set_settings=set_settings />
+                        </div>
                     </div>
+                    <Listen article=article reading=reading set_reading=set_reading />
                 </div>
                 <div class="sm:px-16">
-                    <img
-                        src=move || article().image.url
-                        alt=move || article().image.caption
-                        class="object-cover w-full aspect-[3/2]"
-                    />
-                    <Caption>{move || article().image.caption}</Caption>
+                    {move || {
+                        view! {
+                            <Picture
+                                url=article().image.url
+                                alt=article().image.caption
+                                sizes=ARTICLE_IMAGE_SIZES
+                                class="w-full aspect-[3/2]"
+                                eager=true
+                            />
+                        }
+                    }} <Caption>{move || article().image.caption}</Caption>
                 </div>
                 <Divider />
-                <div class="flex flex-col gap-5 font-serif text-lg/[1.75rem] sm:text-xl/[2rem]
-                [&>div:first-child>p]:first-letter:text-[3.45rem]
-                sm:[&>div:first-child>p]:first-letter:text-[3.9rem]
-                [&>div:first-child>p]:first-letter:leading-none
-                [&>div:first-child>p]:first-letter:font-bold
-                [&>div:first-child>p]:first-letter:font-serif
-                [&>div:first-child>p]:first-letter:float-left
-                [&>div:first-child>p]:first-letter:pr-2">
+                <div class=move || {
+                    format!(
+                        "flex flex-col gap-5 {} {}",
+                        settings().classes(),
+                        if settings().dyslexic { "" } else { DROP_CAP },
+                    )
+                }>
                     {move || {
-                        article()
-                            .fragments
+                        let fragments = &article().fragments;
+                        let mut paragraphs = 0;
+                        fragments
                             .iter()
-                            .map(|fragment| {
-                                match fragment {
+                            .enumerate()
+                            .map(|(index, fragment)| {
+                                let fragment = match fragment {
                                     Fragment::Image(Image { url, caption }) => {
                                         view! {
-                                            <div class="px-16">
-                                                <img src=*url alt=*caption class="object-cover w-full" />
+                                            <div
+                                                class="px-16 transition-colors"
+                                                class=("bg-highlight", move || reading() == Some(index))
+                                            >
+                                                <Picture
+                                                    url=url
+                                                    alt=caption
+                                                    sizes=ARTICLE_IMAGE_SIZES
+                                                    class="object-cover w-full"
+                                                />
                                                 <Caption>{*caption}</Caption>
                                             </div>
                                         }
                                     }
                                     Fragment::Text(text) => {
+                                        paragraphs += 1;
                                         view! {
-                                            <div>
+                                            <div
+                                                class="transition-colors"
+                                                class=("bg-highlight", move || reading() == Some(index))
+                                            >
                                                 <p>{*text}</p>
                                             </div>
                                         }
                                     }
+                                };
+                                let ad = (matches!(fragments[index], Fragment::Text(_))
+                                    && paragraphs % AD_EVERY == 0 && index + 1 < fragments.len())
+                                    .then(|| {
+                                        view! {
+                                            <AdSlot format=Format::Inline topic=article().topic />
+                                        }
+                                    });
+                                view! {
+                                    {fragment}
+                                    {ad}
                                 }
                             })
                             .collect_view()
@@ -515,20 +763,237 @@ pub fn Article() -> impl IntoView {
                 <ReadMore this_article=article />
             </div>
         </div>
+        <div class="hidden p-4 xl:block">
+            <div class="sticky top-4">
+                {move || view! { <AdSlot format=Format::Vertical topic=article().topic /> }}
+            </div>
+        </div>
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Playback {
+    Stopped,
+    Playing,
+    Paused,
+}
+
+/// Reads an article aloud, marking the fragment being read in `reading`.
 #[component]
-pub fn Divider(#[prop(optional)] light: bool) -> impl IntoView {
+pub fn Listen(
+    article: impl Fn() -> &'static Article + Copy + 'static,
+    reading: ReadSignal<Option<usize>>,
+    set_reading: WriteSignal<Option<usize>>,
+) -> impl IntoView {
+    const RATES: [f32; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+    const NORMAL: usize = 1;
+    let (playback, set_playback) = create_signal(Playback::Stopped);
+    let (rate, set_rate) = create_signal(NORMAL);
+    let (captions, set_captions) = create_signal(false);
+    let position = store_value(0);
+    let stop = move || {
+        speech::stop();
+        set_playback(Playback::Stopped);
+        set_reading(None);
+        position.set_value(0);
+    };
+    // Starts reading from the current part, or the same fragment if captions
+    // have been turned on or off since.
+    let play = move || {
+        let script = speech::script(article(), captions.get_untracked());
+        let start = reading
+            .get_untracked()
+            .map_or(position.get_value(), |fragment| {
+                script
+                    .iter()
+                    .position(|(index, _)| index.is_some_and(|index| index >= fragment))
+                    .unwrap_or_default()
+            });
+        let fragments = script.iter().map(|(index, _)| *index).collect_vec();
+        speech::speak(
+            &script,
+            start,
+            RATES[rate.get_untracked()],
+            move |part| {
+                position.set_value(part);
+                set_reading(fragments[part]);
+            },
+            stop,
+        );
+        set_playback(Playback::Playing);
+    };
+    let restart = move || {
+        if playback.get_untracked() != Playback::Stopped {
+            play();
+        }
+    };
+    create_effect(move |_| {
+        article();
+        untrack(stop);
+    });
+    on_cleanup(speech::stop);
     view! {
         <div
-            class="w-full h-px {}"
-            class=("bg-gray-200", light)
-            class=("bg-gray-800", !light)
-        ></div>
+            class="flex flex-wrap items-center gap-3 mt-2 font-sans text-sm"
+            class:hidden=!speech::supported()
+        >
+            <button
+                class="px-3 py-1 rounded text-masthead-ink bg-masthead"
+                on:click=move |_| match playback() {
+                    Playback::Stopped => play(),
+                    Playback::Playing => {
+                        speech::pause();
+                        set_playback(Playback::Paused);
+                    }
+                    Playback::Paused => {
+                        speech::resume();
+                        set_playback(Playback::Playing);
+                    }
+                }
+            >
+                {move || match playback() {
+                    Playback::Stopped => "\u{25B6} Listen",
+                    Playback::Playing => "\u{23F8} Pause",
+                    Playback::Paused => "\u{25B6} Resume",
+                }}
+            </button>
+            <button
+                class="px-3 py-1 border rounded border-rule"
+                class:hidden=move || playback() == Playback::Stopped
+                on:click=move |_| stop()
+            >
+                "\u{25A0} Stop"
+            </button>
+            <select
+                class="bg-transparent"
+                aria-label="Reading speed"
+                on:change=move |event| {
+                    set_rate(event_target_value(&event).parse().unwrap());
+                    restart();
+                }
+            >
+                {RATES
+                    .iter()
+                    .enumerate()
+                    .map(|(index, rate)| {
+                        view! {
+                            <option value=index selected=index == NORMAL class="bg-paper text-ink">
+                                {format!("{rate}\u{d7}")}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+            <label class="flex items-center gap-1">
+                <input
+                    type="checkbox"
+                    prop:checked=captions
+                    on:change=move |event| {
+                        set_captions(event_target_checked(&event));
+                        restart();
+                    }
+                />
+                "Read captions"
+            </label>
+        </div>
+    }
+}
+
+/// A popover for changing how article text is set.
+#[component]
+pub fn ReaderSettings(
+    settings: ReadSignal<Settings>,
+    set_settings: WriteSignal<Settings>,
+) -> impl IntoView {
+    let (open, set_open) = create_signal(false);
+    fn choices<T: Copy + PartialEq + 'static>(
+        label: &'static str,
+        options: &'static [T],
+        name: fn(T) -> &'static str,
+        get: impl Fn() -> T + Copy + 'static,
+        set: impl Fn(T) + Copy + 'static,
+    ) -> impl IntoView {
+        view! {
+            <div class="flex items-center justify-between gap-4">
+                <div>{label}</div>
+                <div class="flex border divide-x rounded border-rule divide-rule">
+                    {options
+                        .iter()
+                        .map(|option| {
+                            view! {
+                                <button
+                                    class="px-2 py-1 min-w-8"
+                                    class=("bg-selected", move || get() == *option)
+                                    aria-pressed=move || (get() == *option).to_string()
+                                    on:click=move |_| set(*option)
+                                >
+                                    {name(*option)}
+                                </button>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </div>
+        }
+    }
+    view! {
+        <div class="relative font-sans">
+            <button
+                class="px-2 font-serif text-lg"
+                aria-label="Text settings"
+                aria-expanded=move || open().to_string()
+                on:click=move |_| set_open.update(|open| *open = !*open)
+            >
+                "Aa"
+            </button>
+            <div
+                class="absolute right-0 z-20 flex flex-col gap-3 p-4 text-sm border shadow top-full bg-paper border-rule w-72"
+                class:hidden=move || !open()
+            >
+                {choices(
+                    "Size",
+                    Size::VARIANTS,
+                    Size::name,
+                    move || settings().size,
+                    move |size| set_settings.update(|settings| settings.size = size),
+                )}
+                {choices(
+                    "Line height",
+                    Spacing::VARIANTS,
+                    Spacing::name,
+                    move || settings().spacing,
+                    move |spacing| set_settings.update(|settings| settings.spacing = spacing),
+                )}
+                {choices(
+                    "Font",
+                    Face::VARIANTS,
+                    Face::name,
+                    move || settings().face,
+                    move |face| set_settings.update(|settings| settings.face = face),
+                )}
+                <label class="flex items-center justify-between gap-4">
+                    "Dyslexia-friendly"
+                    <input
+                        type="checkbox"
+                        prop:checked=move || settings().dyslexic
+                        on:change=move |event| {
+                            set_settings
+                                .update(|settings| {
+                                    settings.dyslexic = event_target_checked(&event);
+                                });
+                        }
+                    />
+                </label>
+            </div>
+        </div>
     }
 }
 
+#[component]
+pub fn Divider(#[prop(optional)] light: bool) -> impl IntoView {
+    view! { <div class="w-full h-px {}" class=("bg-rule", light) class=("bg-ink/80", !light)></div> }
+}
+
 #[component]
 pub fn ReadMore(this_article: impl Fn() -> &'static Article + 'static) -> impl IntoView {
     view! {
@@ -582,10 +1047,22 @@ pub fn CategoryHeading(children: Children) -> impl IntoView {
 
 #[component]
 pub fn Footer(#[prop(optional)] ads: bool) -> impl IntoView {
-    let ad = ADS.choose(&mut thread_rng()).unwrap();
+    let topic = use_params_map().with(|params| {
+        let id = params.get("id")?;
+        ARTICLES
+            .iter()
+            .find(|article| article.id == id)
+            .map(|article| article.topic)
+    });
+    let ad = (ads && !ad::is_banner_closed())
+        .then(|| ad::choose(Format::Horizontal, topic))
+        .flatten();
     let (show_overlay, set_show_overlay) = create_signal(false);
+    let (show_reasons, set_show_reasons) = create_signal(false);
+    let (hidden, set_hidden) = create_signal(false);
+    let (closed, set_closed) = create_signal(false);
     view! {
-        <footer class="flex flex-col p-4 text-white bg-black">
+        <footer class="flex flex-col p-4 text-masthead-ink bg-masthead">
             <A href="/">
                 <Heading>
                     <div class="capitalize font-blackletter">"The Waratah"</div>
@@ -595,20 +1072,33 @@ pub fn Footer(#[prop(optional)] ads: bool) -> impl IntoView {
                 <div>"Copyright \u{a9} 2024"</div>
                 "Brought to you by incredible (and a few credible) reporters."
             </div>
+            <A href="/privacy" class="text-sm underline opacity-75">
+                "Privacy settings"
+            </A>
         </footer>
-        {ads
-            .then_some(
+        {ad
+            .map(|ad| {
+                let image = observed(ad);
                 view! {
-                    <div class="sticky bottom-0 flex justify-center w-full p-2 bg-gray-100 border">
+                    <div
+                        class="sticky bottom-0 flex justify-center w-full p-2 bg-panel border"
+                        class:hidden=closed
+                    >
                         <div class="relative">
                             <div class="relative">
-                                <img
-                                    src=format!("/images/horizontal-ads/{}", *ad)
-                                    class="h-24 cursor-pointer"
-                                />
+                                <AdLink ad=ad>
+                                    <img
+                                        node_ref=image
+                                        src=ad.src()
+                                        alt=ad.alt
+                                        title=ad.advertiser
+                                        class="h-24"
+                                        class:cursor-pointer=ad.url.is_some()
+                                    />
+                                </AdLink>
                                 <div class=move || {
                                     format!(
-                                        "absolute inset-0 z-10 flex flex-col items-center gap-1 p-2 bg-gray-100 border text-neutral-500 {}",
+                                        "absolute inset-0 z-10 flex flex-col items-center gap-1 p-2 bg-panel border text-muted {}",
                                         if show_overlay.get().not() {
                                             "opacity-0 pointer-events-none"
                                         } else {
@@ -618,47 +1108,216 @@ pub fn Footer(#[prop(optional)] ads: bool) -> impl IntoView {
                                 }>
                                     <button
                                         class="absolute top-0 left-0 p-2 text-2xl leading-none"
-                                        on:click=move |_| set_show_overlay(false)
+                                        class:hidden=hidden
+                                        on:click=move |_| {
+                                            if show_reasons() {
+                                                set_show_reasons(false);
+                                            } else {
+                                                set_show_overlay(false);
+                                            }
+                                        }
                                     >
                                         "\u{2190}"
                                     </button>
                                     <h1 class="text-sm">
                                         "Ads not by " <span class="font-bold">"Google"</span>
                                     </h1>
-                                    <div class="flex flex-col w-full gap-1 px-16 text-xs">
-                                        <button
-                                            class="w-full py-1 text-white bg-blue-500 rounded-sm shadow"
-                                            on:click=move |_| set_show_overlay(false)
-                                        >
-                                            "Keep seeing this ad"
-                                        </button>
-                                        <button
-                                            class="w-full py-1 bg-white rounded-sm shadow"
-                                            on:click=move |_| set_show_overlay(false)
-                                        >
-                                            "Why not this ad? \u{25B7}"
-                                        </button>
-                                    </div>
+                                    {move || {
+                                        if hidden() {
+                                            view! {
+                                                <div class="text-xs text-center">
+                                                    "Thanks for letting us know. You won't see this ad again."
+                                                </div>
+                                            }
+                                                .into_view()
+                                        } else if show_reasons() {
+                                            view! {
+                                                <div class="grid w-full grid-cols-2 gap-1 px-16 text-xs">
+                                                    {Reason::VARIANTS
+                                                        .iter()
+                                                        .map(|reason| {
+                                                            view! {
+                                                                <button
+                                                                    class="w-full py-1 bg-paper rounded-sm shadow"
+                                                                    on:click=move |_| {
+                                                                        ad::hide(ad, *reason);
+                                                                        set_hidden(true);
+                                                                    }
+                                                                >
+                                                                    {reason.description()}
+                                                                </button>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </div>
+                                            }
+                                                .into_view()
+                                        } else {
+                                            view! {
+                                                <div class="flex flex-col w-full gap-1 px-16 text-xs">
+                                                    <button
+                                                        class="w-full py-1 rounded-sm shadow text-paper bg-accent"
+                                                        on:click=move |_| set_show_overlay(false)
+                                                    >
+                                                        "Keep seeing this ad"
+                                                    </button>
+                                                    <button
+                                                        class="w-full py-1 bg-paper rounded-sm shadow"
+                                                        on:click=move |_| set_show_reasons(true)
+                                                    >
+                                                        "Why not this ad? \u{25B7}"
+                                                    </button>
+                                                </div>
+                                            }
+                                                .into_view()
+                                        }
+                                    }}
                                 </div>
                             </div>
                             <div class="text-sm text-center opacity-50">"Advertisement"</div>
-                            <button
-                                class="absolute top-0 right-0 flex text-xs leading-none text-blue-500"
-                                on:click=move |_| set_show_overlay(true)
-                            >
-                                <div class="grid border bg-gray-100/50 size-4 place-content-center">
-                                    <div class="cursor-pointer border rounded-full text-[8px] aspect-square size-3 grid place-content-center border-blue-500 font-medium">
+                            <div class="absolute top-0 right-0 flex text-xs leading-none text-accent">
+                                <button
+                                    class="grid border bg-panel/50 size-4 place-content-center"
+                                    aria-label="About this ad"
+                                    on:click=move |_| set_show_overlay(true)
+                                >
+                                    <div class="cursor-pointer border rounded-full text-[8px] aspect-square size-3 grid place-content-center border-accent font-medium">
                                         i
                                     </div>
-                                </div>
-                                <div class="grid border place-content-center bg-gray-100/50 size-4">
+                                </button>
+                                <button
+                                    class="grid border place-content-center bg-panel/50 size-4"
+                                    aria-label="Close ad"
+                                    on:click=move |_| {
+                                        ad::close_banner();
+                                        set_closed(true);
+                                    }
+                                >
                                     "X"
-                                </div>
-                            </button>
+                                </button>
+                            </div>
                         </div>
                     </div>
-                },
-            )}
+                }
+            })}
+    }
+}
+
+/// A reference for an ad's image that reports an impression once it's seen.
+fn observed(ad: &'static Ad) -> NodeRef<Img> {
+    let image = create_node_ref::<Img>();
+    image.on_load(move |image| report::observe(&image, ad));
+    image
+}
+
+/// Links an ad's image to the advertiser and reports the click, if the ad has
+/// somewhere to go.
+#[component]
+fn AdLink(ad: &'static Ad, children: Children) -> impl IntoView {
+    let children = children();
+    match ad.url {
+        Some(url) => view! {
+            <a
+                href=url
+                target="_blank"
+                rel="sponsored noopener"
+                on:click=move |_| report::record(Event::Click, ad)
+            >
+                {children}
+            </a>
+        }
+        .into_view(),
+        None => children.into_view(),
+    }
+}
+
+/// An ad slot labelled as an advertisement, left empty if no ad is running for
+/// its format.
+#[component]
+pub fn AdSlot(
+    format: Format,
+    #[prop(optional, into)] topic: Option<&'static str>,
+) -> impl IntoView {
+    ad::choose(format, topic).map(|ad| {
+        let image = observed(ad);
+        view! {
+            <aside class="flex flex-col items-center gap-1 font-sans" aria-label="Advertisement">
+                <AdLink ad=ad>
+                    <img
+                        node_ref=image
+                        src=ad.src()
+                        alt=ad.alt
+                        title=ad.advertiser
+                        class=match format {
+                            Format::Horizontal => "h-24",
+                            Format::Vertical => "w-40",
+                            Format::Square => "size-[250px]",
+                            Format::Inline => "w-full max-w-xl",
+                        }
+                    />
+                </AdLink>
+                <div class="text-sm opacity-50">"Advertisement"</div>
+            </aside>
+        }
+    })
+}
+
+/// An article image, served from its resized copies in `src/images/cache` if
+/// the build made any, with `sizes` saying how wide it's shown. Unless it's
+/// `eager`, it loads lazily over a blurred placeholder, and a fallback graphic
+/// replaces it if it can't be loaded.
+#[component]
+pub fn Picture(
+    url: &'static str,
+    alt: &'static str,
+    sizes: &'static str,
+    #[prop(optional)] class: &'static str,
+    #[prop(optional)] eager: bool,
+) -> impl IntoView {
+    let variants = picture::variants(url);
+    let (loaded, set_loaded) = create_signal(false);
+    let (failed, set_failed) = create_signal(false);
+    view! {
+        <div class=format!("relative overflow-hidden bg-panel {class}") class=("min-h-32", failed)>
+            {variants
+                .map(|variants| {
+                    view! {
+                        <div
+                            class="absolute inset-0 scale-110 bg-center bg-cover blur-lg"
+                            style=format!("background-image: url({});", variants.placeholder)
+                        ></div>
+                    }
+                })}
+            <picture>
+                {variants
+                    .and_then(|variants| variants.srcset("webp"))
+                    .map(|srcset| {
+                        view! { <source type="image/webp" srcset=srcset sizes=sizes /> }
+                    })}
+                <img
+                    src=variants.map_or_else(|| url.to_string(), Variants::fallback)
+                    srcset=variants.map(Variants::fallback_srcset)
+                    sizes=variants.map(|_| sizes)
+                    width=variants.map(|variants| variants.width)
+                    height=variants.map(|variants| variants.height)
+                    alt=alt
+                    loading=if eager { "eager" } else { "lazy" }
+                    decoding="async"
+                    fetchpriority=eager.then_some("high")
+                    class="relative block object-cover transition-opacity duration-500 size-full"
+                    class=("opacity-0", move || !loaded())
+                    on:load=move |_| set_loaded(true)
+                    on:error=move |_| set_failed(true)
+                />
+            </picture>
+            <div
+                class="absolute inset-0 flex flex-col items-center justify-center gap-1 text-sm bg-panel text-muted"
+                class:hidden=move || !failed()
+            >
+                <img src="/images/missing.svg" alt="" class="size-12" />
+                "Image unavailable"
+            </div>
+        </div>
     }
 }
 
@@ -670,10 +1329,14 @@ pub fn Caption(children: Children) -> impl IntoView {
 #[component]
 #[allow(clippy::needless_lifetimes, clippy::too_many_lines)]
 pub fn CrosswordGrid(
-    grid: Vec<Option<(char, Option<usize>)>>,
+    grid: Vec<Option<Option<usize>>>,
     crossword: &'static Crossword,
-    #[prop(into)] on_solution_change: Callback<HashMap<usize, Option<char>>>,
+    #[prop(into)] on_solution_change: Callback<HashMap<usize, Option<String>>>,
     #[prop(into)] on_selection_change: Callback<Option<Word>>,
+    #[prop(into)] select: Signal<Option<Word>>,
+    /// Cells whose entries have been checked and found wrong.
+    #[prop(into)]
+    wrong: Signal<HashSet<usize>>,
 ) -> impl IntoView {
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     enum Move {
@@ -683,13 +1346,18 @@ pub fn CrosswordGrid(
         Down,
         Next,
         Previous,
+        NextWord,
+        PreviousWord,
+        Stay,
     }
     impl Move {
         const fn direction(self) -> Option<Direction> {
             match self {
                 Self::Left | Self::Right => Some(Direction::Across),
                 Self::Up | Self::Down => Some(Direction::Down),
-                Self::Next | Self::Previous => None,
+                Self::Next | Self::Previous | Self::NextWord | Self::PreviousWord | Self::Stay => {
+                    None
+                }
             }
         }
         const fn new_index(self, crossword_size: Vec2, selected: usize) -> Option<usize> {
@@ -698,16 +1366,20 @@ pub fn CrosswordGrid(
                 Self::Right => Some(selected + 1),
                 Self::Up => Some(selected - (crossword_size.x)),
                 Self::Down => Some(selected + crossword_size.x),
-                Self::Next | Self::Previous => None,
+                Self::Next | Self::Previous | Self::NextWord | Self::PreviousWord | Self::Stay => {
+                    None
+                }
             }
         }
         const fn out_of_bounds(self, crossword_size: Vec2, selected: usize) -> Option<bool> {
             match self {
-                Self::Left => Some(selected % crossword_size.x == 0),
+                Self::Left => Some(selected.is_multiple_of(crossword_size.x)),
                 Self::Right => Some(selected % crossword_size.x == crossword_size.x - 1),
                 Self::Up => Some(selected < crossword_size.x),
                 Self::Down => Some(selected >= crossword_size.x * (crossword_size.y - 1)),
-                Self::Next | Self::Previous => None,
+                Self::Next | Self::Previous | Self::NextWord | Self::PreviousWord | Self::Stay => {
+                    None
+                }
             }
         }
         const fn from_direction(direction: Direction) -> Self {
@@ -727,92 +1399,174 @@ pub fn CrosswordGrid(
                 Self::Down => Self::Up,
                 Self::Next => Self::Previous,
                 Self::Previous => Self::Next,
+                Self::NextWord => Self::PreviousWord,
+                Self::PreviousWord => Self::NextWord,
+                Self::Stay => Self::Stay,
             }
         }
     }
     enum SetSolution {
         Clear,
-        Write(char),
+        Write(String),
+        /// Adds to a rebus cell that holds more than one letter.
+        Append(String),
+        /// Removes the last letter of a rebus cell.
+        Pop,
         Keep,
     }
     let (selected, set_selected) = create_signal(None::<usize>);
     let (solution, set_solution) = create_signal(
         grid.iter()
             .enumerate()
-            .filter_map(|(index, cell)| cell.as_ref().map(|_| (index, None::<char>)))
+            .filter_map(|(index, cell)| cell.as_ref().map(|_| (index, None::<String>)))
             .collect::<HashMap<_, _>>(),
     );
     let (last_direction, set_last_direction) = create_signal(Direction::default());
+    let (rebus, set_rebus) = create_signal(false);
     let size = crossword.size();
+    let position = move |index| Vec2 {
+        x: index % size.x,
+        y: index / size.x,
+    };
+    let word_at = move |index| {
+        crossword
+            .words
+            .iter()
+            .filter(|word| word.contains(position(index)))
+            .find_or_first(|word| word.direction == last_direction())
+    };
     let on_selection_change = move || {
-        let position = move |index| Vec2 {
-            x: index % size.x,
-            y: index / size.x,
-        };
-        on_selection_change(
-            selected()
-                .and_then(|selected| {
-                    crossword
-                        .words
-                        .iter()
-                        .filter(|word| word.contains(position(selected)))
-                        .find_or_first(|word| word.direction == last_direction())
-                })
-                .copied(),
-        );
+        set_rebus(false);
+        on_selection_change(selected().and_then(word_at).copied());
     };
+    let index_of = move |position: Vec2| position.y * size.x + position.x;
+    let is_empty =
+        move |index: &usize| solution.with_untracked(|solution| solution[index].is_none());
+    let first_empty = move |word: &Word| {
+        word.positions()
+            .map(index_of)
+            .find(is_empty)
+            .unwrap_or_else(|| index_of(word.position))
+    };
+    let adjacent_word = move |word: &Word, movement: Move| {
+        let words = crossword
+            .words
+            .iter()
+            .sorted_by_key(|word| {
+                (
+                    crossword.number(word.position),
+                    word.direction == Direction::Down,
+                )
+            })
+            .collect_vec();
+        let index = words.iter().position(|other| *other == word)?;
+        match movement {
+            Move::NextWord => words.get(index + 1).copied(),
+            Move::PreviousWord => index.checked_sub(1).map(|index| words[index]),
+            _ => None,
+        }
+    };
+    create_effect(move |_| {
+        let Some(word) = select() else {
+            return;
+        };
+        if !crossword.words.contains(&word) {
+            return;
+        }
+        untrack(|| {
+            set_last_direction(word.direction);
+            set_selected(Some(first_empty(&word)));
+            on_selection_change();
+        });
+    });
+    let inputs = grid
+        .iter()
+        .map(|_| create_node_ref::<Input>())
+        .collect_vec();
+    let first = grid.iter().position(Option::is_some).unwrap_or_default();
+    let digits = crossword.digits;
     {
-        let grid = grid.clone();
-        let handler = move |event: KeyboardEvent| {
-            let (new, movement) = match event.key().as_str() {
-                key if key.len() == 1 && key.chars().next().unwrap().is_ascii_alphabetic() => (
-                    SetSolution::Write(key.chars().next().unwrap().to_ascii_uppercase()),
-                    Move::Next,
-                ),
-                "ArrowLeft" => (SetSolution::Keep, Move::Left),
-                "ArrowRight" => (SetSolution::Keep, Move::Right),
-                "ArrowUp" => (SetSolution::Keep, Move::Up),
-                "ArrowDown" => (SetSolution::Keep, Move::Down),
-                "Backspace" => (SetSolution::Clear, Move::Previous),
-                "Escape" => {
-                    set_selected(None);
-                    on_selection_change();
-                    event.prevent_default();
-                    return;
+        let inputs = inputs.clone();
+        create_effect(move |_| {
+            if let Some(input) = selected().and_then(|selected| inputs[selected].get()) {
+                input.focus().unwrap();
+            }
+        });
+    }
+    let label = move |index| {
+        let Some(word) = word_at(index) else {
+            return String::new();
+        };
+        let length = word.length();
+        let letter = word
+            .positions()
+            .position(|cell| cell == position(index))
+            .unwrap()
+            + 1;
+        format!(
+            "{} {}, {length} letters, {}. Letter {letter} of {length}, {}",
+            crossword.number(word.position).unwrap(),
+            word.direction,
+            word.clue,
+            solution.with(|solution| {
+                solution[&index]
+                    .clone()
+                    .unwrap_or_else(|| "blank".to_string())
+            }),
+        )
+    };
+    let apply = move |new: SetSolution, movement: Move| {
+        let Some(selected) = selected.get() else {
+            return;
+        };
+        'out: {
+            let apply_move = |movement: Move| {
+                if movement.out_of_bounds(size, selected).unwrap() {
+                    return None;
                 }
-                _ => return,
-            };
-            event.prevent_default();
-            let Some(selected) = selected.get() else {
-                return;
+                set_last_direction(movement.direction().unwrap_or_default());
+                Some(movement.new_index(size, selected).unwrap())
             };
-            'out: {
-                let apply_move = |movement: Move| {
-                    if movement.out_of_bounds(size, selected).unwrap() {
-                        return None;
-                    }
-                    set_last_direction(movement.direction().unwrap_or_default());
-                    Some(movement.new_index(size, selected).unwrap())
-                };
-                let new_selected = match movement {
-                    Move::Next | Move::Previous => {
-                        let position = Vec2 {
-                            x: selected % size.x,
-                            y: selected / size.x,
-                        };
-                        let Some(word) = crossword
-                            .words
-                            .iter()
-                            .find(|word| {
-                                word.contains(position)
-                                    && last_direction.with(|direction| word.direction == *direction)
-                            })
-                            .or_else(|| {
-                                crossword.words.iter().find(|word| word.contains(position))
-                            })
-                        else {
-                            break 'out;
-                        };
+            let new_selected = match movement {
+                Move::Stay => break 'out,
+                Move::NextWord | Move::PreviousWord => {
+                    let Some(word) =
+                        word_at(selected).and_then(|word| adjacent_word(word, movement))
+                    else {
+                        break 'out;
+                    };
+                    set_last_direction(word.direction);
+                    first_empty(word)
+                }
+                Move::Next | Move::Previous => {
+                    let position = Vec2 {
+                        x: selected % size.x,
+                        y: selected / size.x,
+                    };
+                    let Some(word) = crossword
+                        .words
+                        .iter()
+                        .find(|word| {
+                            word.contains(position)
+                                && last_direction.with(|direction| word.direction == *direction)
+                        })
+                        .or_else(|| crossword.words.iter().find(|word| word.contains(position)))
+                    else {
+                        break 'out;
+                    };
+                    let next_empty = matches!(new, SetSolution::Write(_))
+                        .then(|| {
+                            word.positions()
+                                .map(index_of)
+                                .skip_while(|index| *index != selected)
+                                .skip(1)
+                                .find(is_empty)
+                        })
+                        .flatten();
+                    if let Some(next_empty) = next_empty {
+                        set_last_direction(word.direction);
+                        next_empty
+                    } else {
                         match apply_move(match movement {
                             Move::Next => Move::from_direction(word.direction),
                             Move::Previous => -Move::from_direction(word.direction),
@@ -822,180 +1576,505 @@ pub fn CrosswordGrid(
                             Some(new_selected) => new_selected,
                         }
                     }
-                    movement => match apply_move(movement) {
-                        None => break 'out,
-                        Some(new_selected) => new_selected,
-                    },
-                };
-                if Option::is_none(grid.index(new_selected)) {
-                    break 'out;
+                }
+                movement => match apply_move(movement) {
+                    None => break 'out,
+                    Some(new_selected) => new_selected,
+                },
+            };
+            if !solution.with_untracked(|solution| solution.contains_key(&new_selected)) {
+                break 'out;
+            };
+            set_selected(Some(new_selected));
+            on_selection_change();
+        }
+        match new {
+            SetSolution::Keep => {}
+            new => {
+                let mut solution = solution.get();
+                let cell = solution.get_mut(&selected).unwrap();
+                *cell = match new {
+                    SetSolution::Clear => None,
+                    SetSolution::Write(text) => Some(text),
+                    SetSolution::Append(text) => Some(cell.take().unwrap_or_default() + &text),
+                    SetSolution::Pop => cell.take().and_then(|mut text| {
+                        text.pop();
+                        (!text.is_empty()).then_some(text)
+                    }),
+                    SetSolution::Keep => unreachable!(),
                 };
-                set_selected(Some(new_selected));
-                on_selection_change();
+                on_solution_change(solution.clone());
+                set_solution(solution);
             }
-            match new {
-                SetSolution::Keep => {}
-                new @ (SetSolution::Clear | SetSolution::Write(_)) => {
-                    let mut solution = solution.get();
-                    let char = solution.get_mut(&selected).unwrap();
-                    *char = match new {
-                        SetSolution::Clear => None,
-                        SetSolution::Write(char) => Some(char),
-                        SetSolution::Keep => unreachable!(),
+        }
+    };
+    let handler = {
+        let inputs = inputs.clone();
+        move |event: KeyboardEvent| {
+            let (new, movement) = match event.key().as_str() {
+                key if key.chars().count() == 1 && key.chars().all(char::is_alphanumeric) => {
+                    if rebus() {
+                        (SetSolution::Append(key.to_uppercase()), Move::Stay)
+                    } else {
+                        (SetSolution::Write(key.to_uppercase()), Move::Next)
+                    }
+                }
+                "ArrowLeft" => (SetSolution::Keep, Move::Left),
+                "ArrowRight" => (SetSolution::Keep, Move::Right),
+                "ArrowUp" => (SetSolution::Keep, Move::Up),
+                "ArrowDown" => (SetSolution::Keep, Move::Down),
+                "Backspace" if rebus() => (SetSolution::Pop, Move::Stay),
+                "Backspace" => (SetSolution::Clear, Move::Previous),
+                "Insert" => {
+                    set_rebus(!rebus());
+                    event.prevent_default();
+                    return;
+                }
+                "Enter" | "Escape" if rebus() => {
+                    set_rebus(false);
+                    event.prevent_default();
+                    return;
+                }
+                "Tab" => {
+                    let movement = if event.shift_key() {
+                        Move::PreviousWord
+                    } else {
+                        Move::NextWord
                     };
-                    on_solution_change(solution.clone());
-                    set_solution(solution);
+                    // Past the first or last clue, let focus leave the grid as usual.
+                    if selected()
+                        .and_then(word_at)
+                        .and_then(|word| adjacent_word(word, movement))
+                        .is_none()
+                    {
+                        return;
+                    }
+                    (SetSolution::Keep, movement)
                 }
-            }
-        };
-        window_event_listener(keydown, handler);
-    }
+                "Escape" => {
+                    if let Some(input) = selected().and_then(|selected| inputs[selected].get()) {
+                        input.blur().unwrap();
+                    }
+                    set_selected(None);
+                    on_selection_change();
+                    event.prevent_default();
+                    return;
+                }
+                _ => return,
+            };
+            event.prevent_default();
+            apply(new, movement);
+        }
+    };
     view! {
         <div class="flex justify-center w-full p-2 overflow-x-auto">
-            <div class="grid" style=format!("grid-template-columns: repeat({}, auto);", size.x)>
+            <div
+                class="grid"
+                style=format!("grid-template-columns: repeat({}, auto);", size.x)
+                role="grid"
+                aria-label="Crossword"
+                aria-rowcount=size.y
+                aria-colcount=size.x
+                on:keydown=handler
+            >
                 {grid
                     .into_iter()
                     .enumerate()
-                    .map(|(index, cell)| {
-                        cell.map_or_else(
-                            || {
-                                view! {
-                                    <div class="bg-black">
-                                        <button
-                                            class="size-full"
-                                            on:click=move |_| {
-                                                set_selected(None);
-                                                on_selection_change();
-                                            }
-                                        ></button>
-                                    </div>
-                                }
-                            },
-                            |(_, word_start)| {
-                                view! {
-                                    <div
-                                        class=("bg-yellow-200", move || selected() == Some(index))
-                                        class=(
-                                            "bg-blue-200",
-                                            move || {
-                                                let position = move |index| Vec2 {
-                                                    x: index % size.x,
-                                                    y: index / size.x,
-                                                };
-                                                selected()
-                                                    .and_then(|selected| {
-                                                        crossword
-                                                            .words
-                                                            .iter()
-                                                            .filter(|word| word.contains(position(selected)))
-                                                            .find_or_first(|word| word.direction == last_direction())
-                                                    })
-                                                    .map_or(false, |word| word.contains(position(index)))
+                    .chunks(size.x)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(row, cells)| {
+                        view! {
+                            <div role="row" class="contents" aria-rowindex=row + 1>
+                                {cells
+                                    .map(|(index, cell)| {
+                                        cell.map_or_else(
+                                            || {
+                                                view! {
+                                                    <div class="bg-block" role="gridcell" aria-label="Block">
+                                                        <button
+                                                            class="size-full"
+                                                            tabindex=-1
+                                                            aria-hidden="true"
+                                                            on:click=move |_| {
+                                                                set_selected(None);
+                                                                on_selection_change();
+                                                            }
+                                                        ></button>
+                                                    </div>
+                                                }
                                             },
-                                        )
-                                        class="relative text-xl border border-black size-8"
-                                    >
+                                            |word_start| {
+                                                let input = inputs[index];
+                                                view! {
+                                                    <div
+                                                        role="gridcell"
+                                                        aria-selected=move || {
+                                                            (selected() == Some(index)).to_string()
+                                                        }
+                                                        class=("bg-highlight", move || selected() == Some(index))
+                                                        class=(
+                                                            "bg-selected",
+                                                            move || {
+                                                                selected()
+                                                                    .and_then(word_at)
+                                                                    .is_some_and(|word| {
+                                                                        crossword
+                                                                            .linked(*word)
+                                                                            .iter()
+                                                                            .any(|word| word.contains(position(index)))
+                                                                    })
+                                                            },
+                                                        )
+                                                        class="relative text-xl border border-ink size-8"
+                                                        class=(
+                                                            "border-r-4",
+                                                            crossword
+                                                                .bars
+                                                                .contains(&(position(index), Direction::Across)),
+                                                        )
+                                                        class=(
+                                                            "border-b-4",
+                                                            crossword.bars.contains(&(position(index), Direction::Down)),
+                                                        )
+                                                    >
+                                                        <CellMarks
+                                                            circled=crossword.circled.contains(&position(index))
+                                                            shaded=crossword.shaded.contains(&position(index))
+                                                        />
 
-                                        <input
-                                            class="text-center bg-transparent size-full focus:outline-none caret-transparent"
-                                            on:mousedown=move |_| {
-                                                set_last_direction(last_direction().other());
-                                            }
-                                            on:focus=move |_| {
-                                                match selected.get() {
-                                                    Some(selected) if selected == index => set_selected(None),
-                                                    _ => set_selected(Some(index)),
+                                                        <input
+                                                            class="text-center bg-transparent size-full focus:outline-none caret-transparent"
+                                                            class=(
+                                                                "text-incorrect",
+                                                                move || wrong.with(|wrong| wrong.contains(&index)),
+                                                            )
+                                                            class=(
+                                                                "ring-2 ring-inset ring-accent",
+                                                                move || rebus() && selected() == Some(index),
+                                                            )
+                                                            class=(
+                                                                "text-xs",
+                                                                move || {
+                                                                    solution
+                                                                        .with(|solution| {
+                                                                            solution[&index]
+                                                                                .as_ref()
+                                                                                .is_some_and(|text| text.chars().count() > 1)
+                                                                        })
+                                                                },
+                                                            )
+                                                            node_ref=input
+                                                            tabindex=move || {
+                                                                if selected()
+                                                                    .map_or(index == first, |selected| selected == index)
+                                                                {
+                                                                    0
+                                                                } else {
+                                                                    -1
+                                                                }
+                                                            }
+                                                            autocomplete="off"
+                                                            inputmode="none"
+                                                            autocapitalize="characters"
+                                                            spellcheck="false"
+                                                            aria-label=move || label(index)
+                                                            on:mousedown=move |_| {
+                                                                set_last_direction(last_direction().other());
+                                                            }
+                                                            on:focus=move |_| {
+                                                                if selected.get() != Some(index) {
+                                                                    set_selected(Some(index));
+                                                                    on_selection_change();
+                                                                }
+                                                            }
+                                                            value=move || {
+                                                                solution
+                                                                    .get()
+                                                                    .get(&index)
+                                                                    .unwrap()
+                                                                    .clone()
+                                                                    .unwrap_or_default()
+                                                            }
+                                                        />
+                                                        <div
+                                                            class="absolute text-[8px] leading-none opacity-50 inset-0.5 pointer-events-none"
+                                                            aria-hidden="true"
+                                                        >
+                                                            {word_start.map(|index| index + 1)}
+                                                        </div>
+                                                    </div>
                                                 }
-                                                on_selection_change();
-                                            }
-                                            value=move || {
-                                                solution.get().get(&index).unwrap().unwrap_or_default()
-                                            }
-                                        />
-                                        <div class="absolute text-[8px] leading-none opacity-50 inset-0.5 pointer-events-none">
-                                            {word_start.map(|index| index + 1)}
-                                        </div>
-                                    </div>
-                                }
-                            },
-                        )
+                                            },
+                                        )
+                                    })
+                                    .collect_view()}
+                            </div>
+                        }
                     })
                     .collect_view()}
             </div>
         </div>
+        <CrosswordKeyboard
+            digits=digits
+            on_key=Callback::new(move |key| match key {
+                KeyboardKey::Letter(letter) if rebus() => {
+                    apply(SetSolution::Append(letter.to_string()), Move::Stay);
+                }
+                KeyboardKey::Letter(letter) => {
+                    apply(SetSolution::Write(letter.to_string()), Move::Next)
+                }
+                KeyboardKey::Backspace if rebus() => apply(SetSolution::Pop, Move::Stay),
+                KeyboardKey::Backspace => apply(SetSolution::Clear, Move::Previous),
+                KeyboardKey::ToggleDirection => {
+                    set_last_direction(last_direction().other());
+                    on_selection_change();
+                }
+                KeyboardKey::Rebus => set_rebus(!rebus()),
+            })
+        />
+    }
+}
+
+/// Circles and shading drawn over a crossword cell without catching clicks.
+#[component]
+pub fn CellMarks(circled: bool, shaded: bool) -> impl IntoView {
+    view! {
+        <div class="absolute inset-0 pointer-events-none bg-ink/15" class:hidden=!shaded></div>
+        <div
+            class="absolute inset-0 border border-ink rounded-full pointer-events-none"
+            class:hidden=!circled
+        ></div>
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardKey {
+    Letter(char),
+    Backspace,
+    ToggleDirection,
+    Rebus,
+}
+
+/// An A-Z keyboard shown in place of the system one on touch devices, which
+/// would otherwise cover the grid and send unreliable key events.
+#[component]
+pub fn CrosswordKeyboard(
+    #[prop(into)] on_key: Callback<KeyboardKey>,
+    /// Adds a row of digits for puzzles with numbers in the grid.
+    digits: bool,
+) -> impl IntoView {
+    const ROWS: [&str; 3] = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+    const DIGITS: &str = "1234567890";
+    let key = move |key: KeyboardKey, label: Option<&'static str>, content: String| {
+        view! {
+            <button
+                class="grid h-12 bg-panel rounded grow basis-0 place-content-center active:bg-rule"
+                aria-label=label
+                on:mousedown=|event| event.prevent_default()
+                on:click=move |_| on_key(key)
+            >
+                {content}
+            </button>
+        }
+    };
+    view! {
+        <div
+            class="sticky bottom-0 z-10 flex-col hidden gap-1 p-1 text-lg font-semibold bg-panel select-none [@media(pointer:coarse)]:flex"
+            role="group"
+            aria-label="Crossword keyboard"
+        >
+            {digits
+                .then(|| {
+                    view! {
+                        <div class="flex gap-1">
+                            {DIGITS
+                                .chars()
+                                .map(|digit| key(
+                                    KeyboardKey::Letter(digit),
+                                    None,
+                                    digit.to_string(),
+                                ))
+                                .collect_view()}
+                        </div>
+                    }
+                })}
+            {ROWS
+                .iter()
+                .enumerate()
+                .map(|(index, row)| {
+                    view! {
+                        <div class="flex gap-1">
+                            {(index == ROWS.len() - 1)
+                                .then(|| key(
+                                    KeyboardKey::ToggleDirection,
+                                    Some("Switch direction"),
+                                    "\u{21c4}".to_string(),
+                                ))}
+                            {row
+                                .chars()
+                                .map(|letter| key(
+                                    KeyboardKey::Letter(letter),
+                                    None,
+                                    letter.to_string(),
+                                ))
+                                .collect_view()}
+                            {(index == ROWS.len() - 1)
+                                .then(|| key(
+                                    KeyboardKey::Backspace,
+                                    Some("Backspace"),
+                                    "\u{232b}".to_string(),
+                                ))}
+                            {(index == 0)
+                                .then(|| key(
+                                    KeyboardKey::Rebus,
+                                    Some("Enter several letters in one cell"),
+                                    "+".to_string(),
+                                ))}
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
     }
 }
 
 #[component]
 #[allow(clippy::too_many_lines)]
 pub fn Crossword() -> impl IntoView {
-    let crossword = || -> &Crossword {
-        &CROSSWORDS[use_params_map()
-            .with(|params| <usize as FromStr>::from_str(params.get("id").unwrap()).unwrap())]
+    let id = || {
+        use_params_map()
+            .with(|params| <usize as FromStr>::from_str(params.get("id").unwrap()).unwrap())
     };
-    let starts = move || {
-        crossword()
-            .words
-            .iter()
-            .map(|word| word.position)
-            .unique()
-            .sorted_unstable_by(|a, b| a.y.cmp(&b.y).then_with(|| a.x.cmp(&b.x)))
-            .collect_vec()
-    };
-    let grid = {
-        move || {
-            let size = crossword().size();
-            (0..size.y)
-                .flat_map(|y| {
-                    (0..size.x)
-                        .map(|x| {
+    let crossword = move || -> &Crossword { &CROSSWORDS[id()] };
+    let starts = move || crossword().starts();
+    let title = move || format!("The Waratah Crossword #{}", id());
+    let grid = move || crossword().grid();
+    let (solution, set_solution) = create_signal::<HashMap<usize, Option<String>>>(HashMap::new());
+    let (wrong, set_wrong) = create_signal(HashSet::<usize>::new());
+    let index_of = move |position: Vec2| position.y * crossword().size().x + position.x;
+    let correct = create_memo(move |_| {
+        solution.with(|solution| {
+            !solution.is_empty()
+                && crossword().words.iter().all(|word| {
+                    word.positions()
+                        .map(|position| solution.get(&index_of(position)).cloned().flatten())
+                        .collect::<Option<Vec<_>>>()
+                        .is_some_and(|entries| {
                             crossword()
-                                .to_letters()
-                                .iter()
-                                .find(|letter| letter.position == Vec2 { x, y })
-                                .map(|letter| {
-                                    (
-                                        letter.character,
-                                        starts().iter().position(|start| *start == Vec2 { x, y }),
-                                    )
-                                })
+                                .is_correct(word, &entries.iter().map(String::as_str).collect_vec())
                         })
-                        .collect_vec()
                 })
-                .collect_vec()
-        }
+        })
+    });
+    // The answers aren't in the bundle, so exports only include a solution once
+    // the solver has found it.
+    let solved = move || {
+        correct().then(|| {
+            solution.with(|solution| {
+                crossword()
+                    .to_letters()
+                    .into_iter()
+                    .map(|letter| {
+                        (
+                            letter.position,
+                            solution[&index_of(letter.position)].clone().unwrap(),
+                        )
+                    })
+                    .collect::<HashMap<_, _>>()
+            })
+        })
     };
-    let (solution, set_solution) = create_signal::<HashMap<usize, Option<char>>>(HashMap::new());
-    let correct = create_memo(move |_| {
-        !solution.get().is_empty()
-            && solution
-                .get()
+    let started = create_memo(move |_| {
+        id();
+        Local::now()
+    });
+    create_effect(move |_| {
+        if let Some(solved) = solved() {
+            crossword::save_answers(id(), &solved);
+            record(Solve {
+                id: id(),
+                date: Local::now().date_naive(),
+                seconds: started.with_untracked(|started| (Local::now() - *started).num_seconds()),
+            });
+        }
+    });
+    let check_cells = move |_| {
+        set_wrong(solution.with(|solution| {
+            solution
                 .iter()
-                .all(|(index, letter)| match grid().get(*index).unwrap() {
-                    None => true,
-                    Some((char, _)) => letter == &Some(*char),
+                .filter(|(index, entry)| {
+                    let position = Vec2 {
+                        x: *index % crossword().size().x,
+                        y: *index / crossword().size().x,
+                    };
+                    entry.as_deref().is_some_and(|entry| {
+                        crossword().is_cell_correct(position, entry) == Some(false)
+                    })
                 })
-    });
+                .map(|(index, _)| *index)
+                .collect()
+        }));
+    };
     let check = move |event: MouseEvent| {
         let button: HtmlButtonElement = event_target(&event);
         button.set_text_content(Some(format!("{}", correct()).as_str()));
     };
     let (selected, set_selected) = create_signal(None::<Word>);
+    let (clicked, set_clicked) = create_signal(None::<Word>);
     view! {
         <div class="flex flex-col w-full gap-4 p-4 lg:flex-row">
             <div class="flex flex-col gap-2 lg:basis-0 lg:grow">
+                <div
+                    class="sticky top-0 z-10 p-2 bg-selected lg:hidden"
+                    class:hidden=move || selected().is_none()
+                    aria-hidden="true"
+                >
+                    {move || {
+                        selected()
+                            .map(|word| {
+                                view! {
+                                    <span class="font-semibold">
+                                        {crossword().number(word.position)} " "
+                                        {word.direction.to_string()}
+                                    </span>
+                                    " "
+                                    {format!("{} {}", word.clue, word.enumeration())}
+                                }
+                            })
+                    }}
+                </div>
+                {move || {
+                    crossword()
+                        .reveal
+                        .filter(|_| correct())
+                        .map(|reveal| {
+                            view! {
+                                <div class="p-4 border-2 border-ink" role="status">
+                                    <h2 class="text-xl font-semibold">"Theme"</h2>
+                                    {reveal}
+                                </div>
+                            }
+                        })
+                }}
                 {move || {
                     view! {
                         <CrosswordGrid
                             grid=grid()
                             crossword=crossword()
-                            on_solution_change=set_solution
+                            on_solution_change=move |solution| {
+                                set_solution(solution);
+                                set_wrong(HashSet::new());
+                            }
                             on_selection_change=set_selected
+                            select=clicked
+                            wrong=wrong
                         />
                     }
-                }} <div class="flex justify-center has-[:disabled]:hidden">
+                }}
+                <div class="flex justify-center has-[:disabled]:hidden">
                     <button
-                        class="px-4 py-2 text-white bg-black rounded"
+                        class="px-4 py-2 text-masthead-ink bg-masthead rounded"
                         disabled=move || {
                             solution().is_empty()
                                 || solution().iter().any(|(_, letter)| letter.is_none())
@@ -1006,6 +2085,42 @@ pub fn Crossword() -> impl IntoView {
                         "Check"
                     </button>
                 </div>
+                <div
+                    class="flex justify-center"
+                    class:hidden=move || !crossword().can_check_cells()
+                >
+                    <button
+                        class="px-4 py-2 text-masthead-ink bg-masthead rounded"
+                        on:click=check_cells
+                    >
+                        "Check letters"
+                    </button>
+                </div>
+                <div class="flex justify-center gap-4 text-sm text-accent">
+                    <a
+                        href=move || {
+                            format!(
+                                "data:application/json;base64,{}",
+                                STANDARD.encode(crossword().to_ipuz(&title(), solved().as_ref())),
+                            )
+                        }
+                        download=move || format!("waratah-crossword-{}.ipuz", id())
+                    >
+                        "Download .ipuz"
+                    </a>
+                    <a
+                        href=move || {
+                            format!(
+                                "data:application/x-crossword;base64,{}",
+                                STANDARD.encode(crossword().to_puz(&title(), solved().as_ref())),
+                            )
+                        }
+                        download=move || format!("waratah-crossword-{}.puz", id())
+                    >
+                        "Download .puz"
+                    </a>
+                    <A href=move || format!("/crosswords/{}/print", id())>"Print"</A>
+                </div>
             </div>
             <div class="flex justify-center lg:basis-0 lg:grow">
                 <div class="flex flex-col grid-cols-2 gap-2 sm:grid">
@@ -1027,21 +2142,29 @@ pub fn Crossword() -> impl IntoView {
                                                     starts().iter().position(|start| *start == word.position)
                                                 })
                                                 .map(|word| {
-                                                    let current = Some(*word) == selected();
+                                                    let current = selected()
+                                                        .is_some_and(|selected| {
+                                                            crossword().linked(selected).contains(word)
+                                                        });
+                                                    let word = *word;
                                                     view! {
                                                         <div
-                                                            class="pr-2 font-semibold"
-                                                            class=("bg-blue-200", current)
+                                                            class="pr-2 font-semibold cursor-pointer"
+                                                            class=("bg-selected", current)
+                                                            on:click=move |_| set_clicked(Some(word))
                                                         >
                                                             {starts()
                                                                 .iter()
                                                                 .position(|start| *start == word.position)
                                                                 .map(|index| index + 1)}
                                                         </div>
-                                                        <div class=(
-                                                            "bg-blue-200",
-                                                            current,
-                                                        )>{format!("{} ({})", word.clue, word.answer.len())}</div>
+                                                        <div
+                                                            class="cursor-pointer"
+                                                            class=("bg-selected", current)
+                                                            on:click=move |_| set_clicked(Some(word))
+                                                        >
+                                                            {format!("{} {}", word.clue, word.enumeration())}
+                                                        </div>
                                                     }
                                                 })
                                                 .collect_view()}
@@ -1057,3 +2180,673 @@ pub fn Crossword() -> impl IntoView {
         </div>
     }
 }
+
+/// Sends the reader to the newest crossword released by their local date.
+#[component]
+pub fn CrosswordToday() -> impl IntoView {
+    released(Local::now().date_naive()).next().map_or_else(
+        || view! { <div class="p-4">"There is no crossword today."</div> }.into_view(),
+        |id| view! { <Redirect path=format!("/crosswords/{id}") /> }.into_view(),
+    )
+}
+
+#[component]
+pub fn CrosswordArchive() -> impl IntoView {
+    view! {
+        <div class="flex flex-col w-full gap-2 p-4">
+            <CategoryHeading>"Crosswords"</CategoryHeading>
+            <div class="flex gap-4 text-accent">
+                <A href="/crosswords/today">"Today's crossword"</A>
+                <A href="/crosswords/stats">"Your statistics"</A>
+            </div>
+            <Divider light=true />
+            {released(Local::now().date_naive())
+                .map(|id| {
+                    view! {
+                        <A href=format!("/crosswords/{id}") class="flex justify-between gap-4">
+                            <span>{format!("Crossword #{id}")}</span>
+                            <span class="text-muted">
+                                {CROSSWORDS[id]
+                                    .date
+                                    .map(|date| date.format("%B %-d, %Y").to_string())}
+                            </span>
+                        </A>
+                        <Divider light=true />
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn Privacy() -> impl IntoView {
+    view! {
+        <div class="flex flex-col w-full max-w-2xl gap-4 p-4">
+            <CategoryHeading>"Privacy settings"</CategoryHeading>
+            <p>
+                "The Waratah doesn't use cookies. Your choices, crossword times and quiz scores are kept in this browser and nowhere else."
+            </p>
+            <Divider light=true />
+            {Category::VARIANTS
+                .iter()
+                .map(|category| {
+                    view! {
+                        <label class="flex items-start gap-3">
+                            <input
+                                type="checkbox"
+                                class="mt-1"
+                                prop:checked=consent::granted(*category)
+                                on:change=move |event| {
+                                    consent::set(*category, event_target_checked(&event));
+                                }
+                            />
+                            <div>
+                                <div class="font-semibold">{category.name()}</div>
+                                <div class="text-sm text-muted">{category.description()}</div>
+                            </div>
+                        </label>
+                    }
+                })
+                .collect_view()}
+            {analytics::do_not_track()
+                .then(|| {
+                    view! {
+                        <p class="text-sm text-muted">
+                            "Your browser asks sites not to track you, so analytics stay off whatever you choose here."
+                        </p>
+                    }
+                })}
+        </div>
+    }
+}
+
+#[component]
+pub fn CrosswordStats() -> impl IntoView {
+    const BUCKETS: usize = 11;
+    let solves = solves();
+    let (current, longest) = streaks(&solves, Local::now().date_naive());
+    let times = solves.iter().map(|solve| solve.seconds).collect_vec();
+    let time = |seconds: i64| format!("{}:{:02}", seconds / 60, seconds % 60);
+    #[allow(clippy::cast_possible_wrap)]
+    let average = (!times.is_empty()).then(|| times.iter().sum::<i64>() / times.len() as i64);
+    let histogram = histogram(&solves, BUCKETS);
+    let tallest = histogram.iter().copied().max().unwrap_or_default().max(1);
+    view! {
+        <div class="flex flex-col w-full gap-4 p-4">
+            <CategoryHeading>"Your crossword statistics"</CategoryHeading>
+            <div class="grid grid-cols-2 gap-4 sm:grid-cols-5">
+                {[
+                    ("Completed", solves.len().to_string()),
+                    ("Average time", average.map_or_else(|| "-".to_string(), time)),
+                    (
+                        "Best time",
+                        times.iter().copied().min().map_or_else(|| "-".to_string(), time),
+                    ),
+                    ("Current streak", current.to_string()),
+                    ("Longest streak", longest.to_string()),
+                ]
+                    .into_iter()
+                    .map(|(label, value)| {
+                        view! {
+                            <div class="flex flex-col items-center">
+                                <div class="text-3xl font-semibold">{value}</div>
+                                <div class="text-sm text-muted">{label}</div>
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <Divider light=true />
+            <h2 class="font-semibold">"Solve times"</h2>
+            <div class="flex items-end h-40 gap-1" role="img" aria-label="Histogram of solve times">
+                {histogram
+                    .iter()
+                    .enumerate()
+                    .map(|(minutes, count)| {
+                        view! {
+                            <div class="flex flex-col items-center justify-end h-full grow">
+                                <div class="text-xs">{*count}</div>
+                                <div
+                                    class="w-full bg-accent"
+                                    style=format!("height: {}%;", count * 100 / tallest)
+                                ></div>
+                                <div class="text-xs text-muted">
+                                    {if minutes == BUCKETS - 1 {
+                                        format!("{minutes}+")
+                                    } else {
+                                        minutes.to_string()
+                                    }}
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <div class="text-xs text-center text-muted">"Minutes"</div>
+        </div>
+    }
+}
+
+#[component]
+pub fn CrosswordPrint() -> impl IntoView {
+    let id = || {
+        use_params_map()
+            .with(|params| <usize as FromStr>::from_str(params.get("id").unwrap()).unwrap())
+    };
+    // Like the exports, the answers page can only be printed from the reader's
+    // own grid once they've solved it.
+    let solved = move || crossword::answers(id());
+    let (answers, set_answers) = create_signal(false);
+    // The page is always drawn in the light theme, as it will be printed.
+    view! {
+        <div data-theme="light" class="flex justify-center w-full p-4 bg-paper text-ink print:p-0">
+            <div class="flex flex-col w-full max-w-[210mm] gap-4">
+                <div class="flex items-center justify-between gap-4 print:hidden">
+                    <A href=move || format!("/crosswords/{}", id()) class="text-accent">
+                        "\u{2190} Back to puzzle"
+                    </A>
+                    <label
+                        class="flex items-center gap-2"
+                        class=("text-muted", move || solved().is_none())
+                    >
+                        <input
+                            type="checkbox"
+                            disabled=move || solved().is_none()
+                            on:change=move |event| set_answers(event_target_checked(&event))
+                        />
+                        "Include answers page"
+                        {move || solved().is_none().then_some(" (solve it first)")}
+                    </label>
+                    <button
+                        class="px-4 py-2 text-masthead-ink bg-masthead rounded"
+                        on:click=move |_| window().print().unwrap()
+                    >
+                        "Print"
+                    </button>
+                </div>
+                {move || view! { <CrosswordPrintPage id=id() /> }}
+                {move || {
+                    solved()
+                        .filter(|_| answers())
+                        .map(|solved| {
+                            view! {
+                                <div class="break-before-page">
+                                    <CrosswordPrintPage id=id() answers=solved />
+                                </div>
+                            }
+                        })
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn CrosswordPrintPage(
+    id: usize,
+    #[prop(optional)] answers: Option<HashMap<Vec2, String>>,
+) -> impl IntoView {
+    /// Printable width of an A4 page with 15mm margins.
+    const PAGE_WIDTH: f64 = 180.;
+    const MAX_CELL: f64 = 10.;
+    let crossword = &CROSSWORDS[id];
+    let size = crossword.size();
+    #[allow(clippy::cast_precision_loss)]
+    let cell = (PAGE_WIDTH / size.x as f64).min(MAX_CELL);
+    view! {
+        <div class="flex flex-col gap-4 font-serif text-ink [print-color-adjust:exact]">
+            <div class="flex items-baseline justify-between border-b border-ink">
+                <div class="text-3xl font-blackletter">"The Waratah"</div>
+                <div class="text-lg">
+                    {format!("Crossword #{id}")} {answers.is_some().then_some(" \u{b7} Answers")}
+                </div>
+            </div>
+            <div
+                class="grid mx-auto border border-ink w-fit"
+                style=format!("grid-template-columns: repeat({}, {cell}mm);", size.x)
+            >
+                {crossword
+                    .grid()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, cell_data)| {
+                        let position = Vec2 {
+                            x: index % size.x,
+                            y: index / size.x,
+                        };
+                        let style = format!("height: {cell}mm;");
+                        cell_data
+                            .map_or_else(
+                                || view! { <div class="bg-block" style=style.clone()></div> },
+                                |word_start| {
+                                    view! {
+                                        <div
+                                            class="relative grid border border-ink place-content-center"
+                                            class=(
+                                                "border-r-4",
+                                                crossword.bars.contains(&(position, Direction::Across)),
+                                            )
+                                            class=(
+                                                "border-b-4",
+                                                crossword.bars.contains(&(position, Direction::Down)),
+                                            )
+                                            style=format!("{style} font-size: {}mm;", cell * 0.6)
+                                        >
+                                            <CellMarks
+                                                circled=crossword.circled.contains(&position)
+                                                shaded=crossword.shaded.contains(&position)
+                                            />
+                                            <div class="absolute leading-none top-px left-px text-[2.5mm]">
+                                                {word_start.map(|index| index + 1)}
+                                            </div>
+                                            {answers
+                                                .as_ref()
+                                                .and_then(|answers| answers.get(&position).cloned())}
+                                        </div>
+                                    }
+                                },
+                            )
+                    })
+                    .collect_view()}
+            </div>
+            <div class="gap-6 text-sm columns-2">
+                {Direction::ALL
+                    .iter()
+                    .map(|direction| {
+                        view! {
+                            <div class="break-inside-avoid-column">
+                                <h1 class="text-lg font-semibold">{direction.to_string()}</h1>
+                                <div class="grid grid-cols-[auto_minmax(0,1fr)] gap-x-2">
+                                    {crossword
+                                        .words
+                                        .iter()
+                                        .filter(|word| word.direction == *direction)
+                                        .map(|word| (crossword.number(word.position), word))
+                                        .sorted_unstable_by_key(|(number, _)| *number)
+                                        .map(|(number, word)| {
+                                            view! {
+                                                <div class="font-semibold">{number}</div>
+                                                <div>{format!("{} {}", word.clue, word.enumeration())}</div>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn Puzzles() -> impl IntoView {
+    view! {
+        <div class="flex flex-col w-full gap-4 p-4">
+            <div class="flex flex-col gap-2">
+                <CategoryHeading>"Crossword"</CategoryHeading>
+                <A href="/crosswords" class="text-accent">
+                    "All crosswords"
+                </A>
+            </div>
+            {Kind::VARIANTS
+                .iter()
+                .map(|kind| {
+                    view! {
+                        <div class="flex flex-col gap-2">
+                            <CategoryHeading>{kind.name()}</CategoryHeading>
+                            {PUZZLES
+                                .iter()
+                                .filter(|(_, puzzle)| puzzle.kind() == *kind)
+                                .map(|(id, _)| {
+                                    view! {
+                                        <A href=format!("/puzzles/{kind}/{id}") class="text-accent">
+                                            {format!("{} #{id}", kind.name())}
+                                        </A>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+}
+
+#[component]
+pub fn Puzzle() -> impl IntoView {
+    let puzzle = || {
+        use_params_map().with(|params| {
+            let kind = Kind::from_str(params.get("kind")?).ok()?;
+            find(kind, params.get("id")?)
+        })
+    };
+    move || match puzzle() {
+        None => view! { <div class="p-4">"Puzzle not found"</div> }.into_view(),
+        Some(Puzzle::WordSearch(puzzle)) => view! { <WordSearch puzzle=puzzle /> }.into_view(),
+        Some(Puzzle::Headline(puzzle)) => view! { <Headline puzzle=puzzle /> }.into_view(),
+        Some(Puzzle::Sudoku(puzzle)) => view! { <Sudoku puzzle=puzzle /> }.into_view(),
+    }
+}
+
+#[component]
+pub fn WordSearch(puzzle: &'static WordSearch) -> impl IntoView {
+    let size = puzzle.size();
+    let (start, set_start) = create_signal(None::<Vec2>);
+    let (found, set_found) = create_signal(Vec::<(&'static str, Vec<Vec2>)>::new());
+    let select = move |cell: Vec2| {
+        let Some(start) = start() else {
+            set_start(Some(cell));
+            return;
+        };
+        set_start(None);
+        let Some(cells) = WordSearch::line(start, cell) else {
+            return;
+        };
+        if let Some(word) = puzzle.word_at(&cells) {
+            if !found.with(|found| found.iter().any(|(other, _)| *other == word)) {
+                set_found.update(|found| found.push((word, cells)));
+            }
+        }
+    };
+    let is_found =
+        move |cell: Vec2| found.with(|found| found.iter().any(|(_, cells)| cells.contains(&cell)));
+    view! {
+        <div class="flex flex-col items-center w-full gap-4 p-4">
+            <Heading>{puzzle.title}</Heading>
+            <div
+                class="grid border border-ink"
+                style=format!("grid-template-columns: repeat({}, auto);", size.x)
+            >
+                {(0..size.y)
+                    .cartesian_product(0..size.x)
+                    .map(|(y, x)| {
+                        let cell = Vec2 { x, y };
+                        view! {
+                            <button
+                                class="text-xl font-semibold size-8 sm:size-10"
+                                class=("bg-highlight", move || start() == Some(cell))
+                                class=("bg-selected", move || is_found(cell))
+                                on:click=move |_| select(cell)
+                            >
+                                {puzzle.grid[y][x].to_string()}
+                            </button>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <div class="flex flex-wrap justify-center gap-x-4 gap-y-1">
+                {puzzle
+                    .words
+                    .iter()
+                    .map(|word| {
+                        let word = *word;
+                        view! {
+                            <span
+                                class="uppercase"
+                                class=(
+                                    "line-through text-muted",
+                                    move || {
+                                        found
+                                            .with(|found| found.iter().any(|(other, _)| *other == word))
+                                    },
+                                )
+                            >
+                                {word}
+                            </span>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <div
+                class="font-semibold"
+                class:hidden=move || found.with(Vec::len) < puzzle.words.len()
+            >
+                "All words found!"
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn Headline(puzzle: &'static Headline) -> impl IntoView {
+    let (guesses, set_guesses) = create_signal(HashMap::<usize, String>::new());
+    let (solved, set_solved) = create_signal(HashSet::<usize>::new());
+    let (attempts, set_attempts) = create_signal(0);
+    let done =
+        move || solved.with(|solved| puzzle.hidden.iter().all(|index| solved.contains(index)));
+    let check = move |_| {
+        set_attempts.update(|attempts| *attempts += 1);
+        guesses.with(|guesses| {
+            set_solved.update(|solved| {
+                solved.extend(
+                    guesses
+                        .iter()
+                        .filter(|(index, guess)| puzzle.is_correct(**index, guess))
+                        .map(|(index, _)| *index),
+                );
+            });
+        });
+    };
+    view! {
+        <div class="flex flex-col items-center w-full gap-4 p-4">
+            <Heading>"Guess the headline"</Heading>
+            <p class="text-center">{puzzle.article.blurb}</p>
+            <div class="flex flex-wrap items-baseline justify-center font-serif text-2xl gap-x-2 gap-y-2">
+                {puzzle
+                    .words()
+                    .enumerate()
+                    .map(|(index, word)| {
+                        if puzzle.hidden.contains(&index) {
+                            view! {
+                                <input
+                                    class="text-center bg-transparent border-b-2 border-ink focus:outline-none"
+                                    class=(
+                                        "border-correct",
+                                        move || solved.with(|solved| solved.contains(&index)),
+                                    )
+                                    style=format!("width: {}ch;", word.chars().count() + 1)
+                                    aria-label=format!(
+                                        "Word {}, {} letters",
+                                        index + 1,
+                                        word.chars().count(),
+                                    )
+                                    readonly=move || solved.with(|solved| solved.contains(&index))
+                                    on:input=move |event| {
+                                        let guess = event_target_value(&event);
+                                        set_guesses
+                                            .update(|guesses| {
+                                                guesses.insert(index, guess);
+                                            });
+                                    }
+                                />
+                            }
+                                .into_view()
+                        } else {
+                            view! { <span>{word}</span> }.into_view()
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <button
+                class="px-4 py-2 text-masthead-ink bg-masthead rounded"
+                class:hidden=done
+                on:click=check
+            >
+                "Check"
+            </button>
+            <div class="flex flex-col items-center gap-2" class:hidden=move || !done()>
+                <div class="font-semibold">
+                    {move || format!("Solved in {} guesses!", attempts())}
+                </div>
+                <A href=format!("/articles/{}", puzzle.article.id) class="text-accent">
+                    "Read the article"
+                </A>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn Sudoku(puzzle: &'static Sudoku) -> impl IntoView {
+    let (digits, set_digits) = create_signal(puzzle.givens.clone());
+    let solved = move || digits.with(|digits| puzzle.is_solved(digits));
+    view! {
+        <div class="flex flex-col items-center w-full gap-4 p-4">
+            <Heading>"Mini sudoku"</Heading>
+            <div
+                class="grid border-2 border-ink"
+                style=format!("grid-template-columns: repeat({}, auto);", puzzle.size)
+            >
+                {puzzle
+                    .givens
+                    .iter()
+                    .enumerate()
+                    .map(|(index, given)| {
+                        let (right, bottom) = puzzle.edges(index);
+                        view! {
+                            <input
+                                class="text-xl text-center bg-transparent border border-muted size-10 focus:outline-none focus:bg-highlight"
+                                class=("border-r-ink border-r-2", right)
+                                class=("border-b-ink border-b-2", bottom)
+                                class=("font-bold", given.is_some())
+                                inputmode="numeric"
+                                maxlength="1"
+                                readonly=given.is_some()
+                                aria-label=format!(
+                                    "Row {}, column {}",
+                                    index / puzzle.size + 1,
+                                    index % puzzle.size + 1,
+                                )
+                                value=given.map(|given| given.to_string())
+                                on:input=move |event| {
+                                    let digit = event_target_value(&event)
+                                        .parse::<u8>()
+                                        .ok()
+                                        .filter(|digit| {
+                                            (1..=puzzle.size).contains(&usize::from(*digit))
+                                        });
+                                    set_digits.update(|digits| digits[index] = digit);
+                                }
+                            />
+                        }
+                    })
+                    .collect_view()}
+            </div>
+            <div class="font-semibold" class:hidden=move || !solved()>
+                "Solved!"
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn Quiz() -> impl IntoView {
+    let Some(quiz) = QUIZ.as_ref() else {
+        return view! { <div class="p-4">"There is no quiz this week."</div> }.into_view();
+    };
+    let total = quiz.questions.len();
+    let first_score = quiz::score(quiz.week);
+    let (chosen, set_chosen) = create_signal(vec![None::<usize>; total]);
+    let score = move || {
+        chosen.with(|chosen| {
+            chosen
+                .iter()
+                .zip(&quiz.questions)
+                .filter(|(chosen, question)| **chosen == Some(question.correct))
+                .count()
+        })
+    };
+    let finished = move || chosen.with(|chosen| chosen.iter().all(Option::is_some));
+    create_effect(move |_| {
+        if finished() {
+            quiz::record(quiz.week, score());
+        }
+    });
+    let share = move || {
+        String::from(encode_uri_component(&format!(
+            "I scored {}/{total} on The Waratah news quiz for the week of {}. {}/quiz",
+            score(),
+            quiz.week,
+            location().origin().unwrap(),
+        )))
+    };
+    view! {
+        <div class="flex flex-col w-full gap-6 p-4">
+            <Heading>"News quiz"</Heading>
+            <div class="text-muted">
+                {format!("Week of {}", quiz.week)}
+                {first_score
+                    .map(|score| format!(" \u{b7} You scored {score}/{total} the first time"))}
+            </div>
+            {quiz
+                .questions
+                .iter()
+                .enumerate()
+                .map(|(number, question)| {
+                    let answered = move || chosen.with(|chosen| chosen[number].is_some());
+                    view! {
+                        <div class="flex flex-col gap-2">
+                            <CategoryHeading>
+                                {format!("{}. {}", number + 1, question.text)}
+                            </CategoryHeading>
+                            {question
+                                .answers
+                                .iter()
+                                .enumerate()
+                                .map(|(index, answer)| {
+                                    view! {
+                                        <button
+                                            class="px-4 py-2 text-left border border-ink rounded"
+                                            class=(
+                                                "bg-correct/25",
+                                                move || answered() && index == question.correct,
+                                            )
+                                            class=(
+                                                "bg-incorrect/25",
+                                                move || {
+                                                    index != question.correct
+                                                        && chosen.with(|chosen| chosen[number] == Some(index))
+                                                },
+                                            )
+                                            disabled=answered
+                                            on:click=move |_| {
+                                                set_chosen.update(|chosen| chosen[number] = Some(index));
+                                            }
+                                        >
+                                            {*answer}
+                                        </button>
+                                    }
+                                })
+                                .collect_view()}
+                            <div class="text-accent" class:hidden=move || !answered()>
+                                <A href=format!(
+                                    "/articles/{}",
+                                    question.article.id,
+                                )>{format!("Read the story: {}", question.article.title)}</A>
+                            </div>
+                        </div>
+                    }
+                })
+                .collect_view()}
+            <div class="flex flex-col items-center gap-2" class:hidden=move || !finished()>
+                <div class="text-2xl font-semibold">
+                    {move || format!("You scored {}/{total}", score())}
+                </div>
+                <div class="flex gap-4 text-accent">
+                    <a
+                        href=move || format!("https://twitter.com/intent/tweet?text={}", share())
+                        target="_blank"
+                    >
+                        "Share on X"
+                    </a>
+                    <a href=move || format!("mailto:?body={}", share())>"Share by email"</a>
+                </div>
+            </div>
+        </div>
+    }
+        .into_view()
+}
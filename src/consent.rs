@@ -0,0 +1,90 @@
+use itertools::Itertools;
+use strum::{Display, EnumString, VariantArray};
+
+use crate::storage::storage;
+
+/// The reader's choices, kept in `localStorage` as `category granted` lines.
+const KEY: &str = "consent";
+
+/// The kinds of tracking a reader can agree to. Nothing in a category runs
+/// until they have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Category {
+    Analytics,
+    PersonalisedAds,
+}
+
+impl Category {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Analytics => "Analytics",
+            Self::PersonalisedAds => "Personalised ads",
+        }
+    }
+
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Analytics => {
+                "Tells us which pages you visit and how far you read each article, without \
+                 cookies or anything that identifies you. Always off if your browser asks \
+                 sites not to track you."
+            }
+            Self::PersonalisedAds => "Tells advertisers when their ads are seen or clicked.",
+        }
+    }
+}
+
+fn choices() -> Vec<(Category, bool)> {
+    storage()
+        .and_then(|storage| storage.get_item(KEY).ok().flatten())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (category, granted) = line.split_once(' ')?;
+            Some((category.parse().ok()?, granted.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Whether the reader has made a choice yet, e.g. to show the banner on their
+/// first visit.
+pub fn asked() -> bool {
+    !choices().is_empty()
+}
+
+pub fn granted(category: Category) -> bool {
+    choices()
+        .into_iter()
+        .any(|(other, granted)| other == category && granted)
+}
+
+/// Records a choice for every category, leaving unchosen ones denied.
+pub fn set(category: Category, granted: bool) {
+    let Some(storage) = storage() else {
+        return;
+    };
+    let choices = choices();
+    storage
+        .set_item(
+            KEY,
+            &Category::VARIANTS
+                .iter()
+                .map(|other| {
+                    let granted = if *other == category {
+                        granted
+                    } else {
+                        choices.contains(&(*other, true))
+                    };
+                    format!("{other} {granted}")
+                })
+                .join("\n"),
+        )
+        .unwrap();
+}
+
+pub fn set_all(granted: bool) {
+    for category in Category::VARIANTS {
+        set(*category, granted);
+    }
+}
@@ -1,112 +1,221 @@
-use std::collections::HashSet;
-use std::io::Read;
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::collections::{HashMap, HashSet};
+use std::iter::once;
 use std::str::FromStr;
 
+use chrono::NaiveDate;
+use itertools::Itertools;
 use lazy_static::lazy_static;
-use strum::{Display, VariantArray};
+use serde_json::{json, Map, Value};
+
+mod check;
+#[cfg(test)]
+mod generate;
+mod grid;
+#[cfg(test)]
+mod import;
+
+use crate::storage::storage;
+use check::digest;
+pub use grid::{cells, Direction, Letter, Vec2, Word};
 
 #[derive(Debug, Clone)]
 pub struct Crossword {
     pub words: Vec<Word>,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Word {
-    pub answer: &'static str,
-    pub clue: &'static str,
-    pub direction: Direction,
-    pub position: Vec2,
-}
-
-impl Word {
-    pub const fn contains(&self, position: Vec2) -> bool {
-        let end = match self.direction {
-            Direction::Across => Vec2 {
-                x: self.position.x + self.answer.len() - 1,
-                y: self.position.y,
-            },
-            Direction::Down => Vec2 {
-                x: self.position.x,
-                y: self.position.y + self.answer.len() - 1,
-            },
-        };
-        self.position.x <= position.x
-            && position.x <= end.x
-            && self.position.y <= position.y
-            && position.y <= end.y
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Letter {
-    pub character: char,
-    pub position: Vec2,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, VariantArray, Display, Default)]
-pub enum Direction {
-    #[default]
-    Across,
-    Down,
-}
-
-impl Direction {
-    pub const ALL: &'static [Self] = Self::VARIANTS;
-    pub const fn other(self) -> Self {
-        match self {
-            Self::Across => Self::Down,
-            Self::Down => Self::Across,
-        }
-    }
+    pub circled: HashSet<Vec2>,
+    pub shaded: HashSet<Vec2>,
+    /// Bars drawn after a cell, on its right-hand edge for `Across` and its
+    /// bottom edge for `Down`.
+    pub bars: HashSet<(Vec2, Direction)>,
+    /// Groups of words that are clued together, e.g. "see 5 Down".
+    pub links: Vec<Vec<Word>>,
+    /// Shown once the crossword has been solved.
+    pub reveal: Option<&'static str>,
+    /// The salt and digest of each word's answer, keyed by its start and
+    /// direction.
+    checks: HashMap<(Vec2, Direction), (&'static str, &'static str)>,
+    /// The salt and digest of each cell, for puzzles that allow checking single
+    /// letters.
+    cell_checks: HashMap<Vec2, (&'static str, &'static str)>,
+    /// Whether any answer contains a digit.
+    pub digits: bool,
+    pub date: Option<NaiveDate>,
 }
 
 impl Crossword {
+    /// Parses the `pattern x y direction salt digest clue` lines written by the
+    /// build script, along with `!circle x y`, `!shade x y`, `!bar x y
+    /// direction`, `!link 1 across 5 down ...` and `!reveal text` lines for
+    /// themed puzzles, `!cell x y salt digest` lines for per-cell checking, a
+    /// `!digits` line and a `!date YYYY-MM-DD` release date.
     fn from_str(s: &'static str) -> Self {
-        Self {
-            words: s
-                .trim()
-                .lines()
+        let (extras, words): (Vec<_>, Vec<_>) =
+            s.trim().lines().partition(|line| line.starts_with('!'));
+        let mut checks = HashMap::new();
+        let mut crossword = Self {
+            words: words
+                .into_iter()
                 .map(|line| {
-                    let mut parts = line.splitn(5, |char: char| char.is_whitespace());
-                    Word {
-                        answer: parts.next().unwrap().to_ascii_uppercase().leak(),
+                    let mut parts = line.splitn(7, |char: char| char.is_whitespace());
+                    let answer = parts.next().unwrap();
+                    let word = Word {
+                        answer,
+                        cells: cells(answer)
+                            .into_iter()
+                            .map(|cell| &*cell.leak())
+                            .collect::<Vec<_>>()
+                            .leak(),
                         position: Vec2 {
                             x: usize::from_str(parts.next().unwrap()).unwrap(),
                             y: usize::from_str(parts.next().unwrap()).unwrap(),
                         },
-                        direction: match parts.next().unwrap() {
-                            "across" => Direction::Across,
-                            "down" => Direction::Down,
-                            _ => unreachable!(),
-                        },
+                        direction: direction(parts.next().unwrap()),
+                        clue: "",
+                    };
+                    checks.insert(
+                        (word.position, word.direction),
+                        (parts.next().unwrap(), parts.next().unwrap()),
+                    );
+                    Word {
                         clue: parts.next().unwrap(),
+                        ..word
                     }
                 })
                 .collect(),
+            checks,
+            cell_checks: HashMap::new(),
+            digits: false,
+            date: None,
+            circled: HashSet::new(),
+            shaded: HashSet::new(),
+            bars: HashSet::new(),
+            links: Vec::new(),
+            reveal: None,
+        };
+        for line in extras {
+            let (kind, rest) = line[1..].split_once(' ').unwrap_or((&line[1..], ""));
+            let mut parts = rest.split_whitespace();
+            let mut position = || Vec2 {
+                x: usize::from_str(parts.next().unwrap()).unwrap(),
+                y: usize::from_str(parts.next().unwrap()).unwrap(),
+            };
+            match kind {
+                "circle" => {
+                    crossword.circled.insert(position());
+                }
+                "shade" => {
+                    crossword.shaded.insert(position());
+                }
+                "bar" => {
+                    let position = position();
+                    crossword
+                        .bars
+                        .insert((position, direction(parts.next().unwrap())));
+                }
+                "link" => {
+                    let link = parts
+                        .tuples()
+                        .map(|(number, direction_name)| {
+                            let number = usize::from_str(number).unwrap();
+                            *crossword
+                                .words
+                                .iter()
+                                .find(|word| {
+                                    crossword.number(word.position) == Some(number)
+                                        && word.direction == direction(direction_name)
+                                })
+                                .unwrap()
+                        })
+                        .collect();
+                    crossword.links.push(link);
+                }
+                "reveal" => crossword.reveal = Some(rest.trim()),
+                "cell" => {
+                    let position = position();
+                    crossword
+                        .cell_checks
+                        .insert(position, (parts.next().unwrap(), parts.next().unwrap()));
+                }
+                "digits" => crossword.digits = true,
+                "date" => {
+                    crossword.date =
+                        Some(NaiveDate::parse_from_str(rest.trim(), "%Y-%m-%d").unwrap());
+                }
+                _ => unreachable!(),
+            }
         }
+        crossword
+    }
+
+    /// Whether `entries`, the contents of each of the word's cells, match its
+    /// answer.
+    pub fn is_correct(&self, word: &Word, entries: &[&str]) -> bool {
+        let (salt, hash) = self.checks[&(word.position, word.direction)];
+        digest(salt, entries) == hash
+    }
+
+    /// Whether a single cell's entry is right, or `None` if the setter hasn't
+    /// allowed single letters to be checked.
+    pub fn is_cell_correct(&self, position: Vec2, entry: &str) -> Option<bool> {
+        self.cell_checks
+            .get(&position)
+            .map(|(salt, hash)| digest(salt, &[entry]) == *hash)
+    }
+
+    pub fn can_check_cells(&self) -> bool {
+        !self.cell_checks.is_empty()
+    }
+
+    /// Every word clued together with `word`, in the order the setter linked
+    /// them, or just `word` itself.
+    pub fn linked(&self, word: Word) -> Vec<Word> {
+        self.links
+            .iter()
+            .find(|link| link.contains(&word))
+            .cloned()
+            .unwrap_or_else(|| vec![word])
     }
 
     pub fn to_letters(&self) -> HashSet<Letter> {
         self.words
             .iter()
             .flat_map(|word| {
-                word.answer
-                    .chars()
-                    .enumerate()
-                    .map(move |(index, character)| Letter {
-                        character,
-                        position: match word.direction {
-                            Direction::Across => Vec2 {
-                                x: word.position.x + index,
-                                y: word.position.y,
-                            },
-                            Direction::Down => Vec2 {
-                                x: word.position.x,
-                                y: word.position.y + index,
-                            },
-                        },
-                    })
+                word.cells
+                    .iter()
+                    .zip(word.positions())
+                    .map(|(cell, position)| Letter { cell, position })
+            })
+            .collect()
+    }
+
+    pub fn starts(&self) -> Vec<Vec2> {
+        self.words
+            .iter()
+            .map(|word| word.position)
+            .unique()
+            .sorted_unstable_by(|a, b| a.y.cmp(&b.y).then_with(|| a.x.cmp(&b.x)))
+            .collect()
+    }
+
+    pub fn number(&self, position: Vec2) -> Option<usize> {
+        self.starts()
+            .iter()
+            .position(|start| *start == position)
+            .map(|index| index + 1)
+    }
+
+    /// The grid in row-major order, with `None` for blocks and each letter cell
+    /// holding the index of the word start at that cell, if any.
+    pub fn grid(&self) -> Vec<Option<Option<usize>>> {
+        let size = self.size();
+        let letters = self.to_letters();
+        let starts = self.starts();
+        (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| Vec2 { x, y }))
+            .map(|position| {
+                letters
+                    .iter()
+                    .find(|letter| letter.position == position)
+                    .map(|_| starts.iter().position(|start| *start == position))
             })
             .collect()
     }
@@ -116,12 +225,12 @@ impl Crossword {
         for word in &self.words {
             let end = match word.direction {
                 Direction::Across => Vec2 {
-                    x: word.position.x + word.answer.len(),
+                    x: word.position.x + word.length(),
                     y: word.position.y,
                 },
                 Direction::Down => Vec2 {
                     x: word.position.x,
-                    y: word.position.y + word.answer.len(),
+                    y: word.position.y + word.length(),
                 },
             };
             size.x = size.x.max(end.x);
@@ -129,57 +238,457 @@ impl Crossword {
         }
         size
     }
-}
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
-pub struct Vec2 {
-    pub x: usize,
-    pub y: usize,
-}
 
-impl Add for Vec2 {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    fn is_filled(&self, position: Vec2) -> bool {
+        self.words.iter().any(|word| word.contains(position))
+    }
+
+    /// Encodes the crossword as ipuz, including the solution only when one is
+    /// given, since the answers themselves are not part of the bundle.
+    pub fn to_ipuz(&self, title: &str, solution: Option<&HashMap<Vec2, String>>) -> String {
+        let size = self.size();
+        let starts = self.starts();
+        let rows = |cell: &dyn Fn(Vec2) -> Value| {
+            (0..size.y)
+                .map(|y| (0..size.x).map(|x| cell(Vec2 { x, y })).collect_vec())
+                .collect_vec()
+        };
+        let puzzle = rows(&|position| match self.is_filled(position) {
+            false => json!("#"),
+            true => {
+                let number = starts
+                    .iter()
+                    .position(|start| *start == position)
+                    .map_or(0, |index| index + 1);
+                let mut style = Map::new();
+                if self.circled.contains(&position) {
+                    style.insert("shapebg".into(), json!("circle"));
+                }
+                if self.shaded.contains(&position) {
+                    style.insert("highlight".into(), json!(true));
+                }
+                let barred: String = [(Direction::Across, 'R'), (Direction::Down, 'B')]
+                    .into_iter()
+                    .filter(|(direction, _)| self.bars.contains(&(position, *direction)))
+                    .map(|(_, side)| side)
+                    .collect();
+                if !barred.is_empty() {
+                    style.insert("barred".into(), json!(barred));
+                }
+                if style.is_empty() {
+                    json!(number)
+                } else {
+                    json!({ "cell": number, "style": style })
+                }
+            }
+        });
+        let clues: Map<_, _> = Direction::ALL
+            .iter()
+            .map(|direction| {
+                (
+                    direction.to_string(),
+                    self.words
+                        .iter()
+                        .filter(|word| word.direction == *direction)
+                        .map(|word| (self.number(word.position).unwrap(), word))
+                        .sorted_unstable_by_key(|(number, _)| *number)
+                        .map(|(number, word)| {
+                            let mut clue = json!({
+                                "number": number,
+                                "clue": word.clue,
+                                "enumeration": word
+                                    .enumeration()
+                                    .trim_matches(['(', ')']),
+                                "cells": word
+                                    .positions()
+                                    .map(|position| [position.x + 1, position.y + 1])
+                                    .collect_vec(),
+                            });
+                            let linked = self.linked(*word);
+                            if linked.len() > 1 && linked[0] == *word {
+                                clue["continued"] = linked[1..]
+                                    .iter()
+                                    .map(|word| {
+                                        json!({
+                                            "direction": word.direction.to_string(),
+                                            "number": self.number(word.position),
+                                        })
+                                    })
+                                    .collect();
+                            }
+                            clue
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        let mut ipuz = json!({
+            "version": "http://ipuz.org/v2",
+            "kind": ["http://ipuz.org/crossword#1"],
+            "title": title,
+            "publisher": "The Waratah",
+            "dimensions": { "width": size.x, "height": size.y },
+            "puzzle": puzzle,
+            "clues": clues,
+        });
+        if let Some(solution) = solution {
+            ipuz["solution"] = json!(rows(&|position| json!(solution
+                .get(&position)
+                .map_or("#", String::as_str))));
         }
+        if let Some(reveal) = self.reveal {
+            ipuz["explanation"] = json!(reveal);
+        }
+        if let Some(date) = self.date {
+            ipuz["date"] = json!(date.format("%m/%d/%Y").to_string());
+        }
+        ipuz.to_string()
     }
-}
 
-impl AddAssign for Vec2 {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = *self + rhs;
+    /// Encodes the crossword in the Across Lite binary format. The format numbers
+    /// entries implicitly from the grid, so any run of letters without a matching
+    /// word is given an empty clue. Rebus cells keep only their first letter.
+    /// Without a solution every letter cell is written as `X` and the header
+    /// marks the solution as not provided, so apps let the puzzle be solved but
+    /// not checked.
+    pub fn to_puz(&self, title: &str, solution: Option<&HashMap<Vec2, String>>) -> Vec<u8> {
+        const BLACK: u8 = b'.';
+        const EMPTY: u8 = b'-';
+        const NO_SOLUTION: u16 = 0x0002;
+        fn latin1(text: &str) -> Vec<u8> {
+            text.chars()
+                .map(|char| u8::try_from(char).unwrap_or(b'?'))
+                .collect()
+        }
+
+        let size = self.size();
+        let letters = solution;
+        let positions = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| Vec2 { x, y }))
+            .collect_vec();
+        let solution = positions
+            .iter()
+            .map(|position| {
+                if !self.is_filled(*position) {
+                    return BLACK;
+                }
+                letters
+                    .and_then(|letters| letters.get(position))
+                    .and_then(|cell| cell.chars().next())
+                    .and_then(|char| u8::try_from(char).ok())
+                    .unwrap_or(b'X')
+            })
+            .collect_vec();
+        let state = solution
+            .iter()
+            .map(|cell| if *cell == BLACK { BLACK } else { EMPTY })
+            .collect_vec();
+        let clues = positions
+            .iter()
+            .flat_map(|position| {
+                Direction::ALL.iter().filter_map(|direction| {
+                    let (previous, next) = match direction {
+                        Direction::Across => (
+                            position.x.checked_sub(1).map(|x| Vec2 { x, ..*position }),
+                            Vec2 {
+                                x: position.x + 1,
+                                ..*position
+                            },
+                        ),
+                        Direction::Down => (
+                            position.y.checked_sub(1).map(|y| Vec2 { y, ..*position }),
+                            Vec2 {
+                                y: position.y + 1,
+                                ..*position
+                            },
+                        ),
+                    };
+                    (self.is_filled(*position)
+                        && previous.is_none_or(|previous| !self.is_filled(previous))
+                        && self.is_filled(next))
+                    .then(|| {
+                        self.words
+                            .iter()
+                            .find(|word| word.position == *position && word.direction == *direction)
+                            .map_or("", |word| word.clue)
+                    })
+                })
+            })
+            .map(latin1)
+            .collect_vec();
+        let strings = [
+            latin1(title),
+            latin1("The Waratah"),
+            latin1("\u{a9} The Waratah"),
+        ];
+
+        let mut header = [0; 0x34];
+        header[0x02..0x0E].copy_from_slice(b"ACROSS&DOWN\0");
+        header[0x18..0x1C].copy_from_slice(b"1.3\0");
+        header[0x2C] = u8::try_from(size.x).unwrap();
+        header[0x2D] = u8::try_from(size.y).unwrap();
+        header[0x2E..0x30].copy_from_slice(&u16::try_from(clues.len()).unwrap().to_le_bytes());
+        header[0x30..0x32].copy_from_slice(&1_u16.to_le_bytes());
+        if letters.is_none() {
+            header[0x32..0x34].copy_from_slice(&NO_SOLUTION.to_le_bytes());
+        }
+
+        let cib = checksum(&header[0x2C..0x34], 0);
+        let text = |initial| {
+            let initial = strings.iter().fold(initial, |initial, string| {
+                checksum(&[string.as_slice(), b"\0"].concat(), initial)
+            });
+            clues
+                .iter()
+                .fold(initial, |initial, clue| checksum(clue, initial))
+        };
+        let overall = text(checksum(&state, checksum(&solution, cib)));
+        header[0x00..0x02].copy_from_slice(&overall.to_le_bytes());
+        header[0x0E..0x10].copy_from_slice(&cib.to_le_bytes());
+        let masked = [cib, checksum(&solution, 0), checksum(&state, 0), text(0)];
+        for (index, checksum) in masked.into_iter().enumerate() {
+            let [low, high] = checksum.to_le_bytes();
+            header[0x10 + index] = b"ICHE"[index] ^ low;
+            header[0x14 + index] = b"ATED"[index] ^ high;
+        }
+
+        [
+            header.as_slice(),
+            &solution,
+            &state,
+            &strings
+                .into_iter()
+                .chain(clues)
+                .chain(once(Vec::new()))
+                .flat_map(|string| [string, vec![0]].concat())
+                .collect_vec(),
+        ]
+        .concat()
     }
 }
 
-impl Sub for Vec2 {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+/// The rotating sum Across Lite uses for each of its checksums.
+fn checksum(data: &[u8], initial: u16) -> u16 {
+    data.iter().fold(initial, |checksum, byte| {
+        checksum.rotate_right(1).wrapping_add(u16::from(*byte))
+    })
+}
+
+fn direction(name: &str) -> Direction {
+    match name {
+        "across" => Direction::Across,
+        "down" => Direction::Down,
+        _ => unreachable!(),
     }
 }
 
-impl SubAssign for Vec2 {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = *self - rhs;
+const ANSWERS_KEY: &str = "crossword-answers";
+
+/// The contents of each cell as the reader filled in a crossword they solved,
+/// kept in `localStorage` as `id x y cell` lines, since the bundle doesn't hold
+/// the answers.
+pub fn answers(id: usize) -> Option<HashMap<Vec2, String>> {
+    let answers: HashMap<_, _> = storage()?
+        .get_item(ANSWERS_KEY)
+        .ok()??
+        .lines()
+        .filter_map(|line| {
+            let (other, x, y, cell) = line.split(' ').collect_tuple()?;
+            if usize::from_str(other).ok()? != id {
+                return None;
+            }
+            Some((
+                Vec2 {
+                    x: usize::from_str(x).ok()?,
+                    y: usize::from_str(y).ok()?,
+                },
+                cell.to_string(),
+            ))
+        })
+        .collect();
+    (!answers.is_empty()).then_some(answers)
+}
+
+/// Keeps the reader's answers to a solved crossword, unless they're already
+/// kept.
+pub fn save_answers(id: usize, answers: &HashMap<Vec2, String>) {
+    let Some(storage) = storage() else {
+        return;
+    };
+    if self::answers(id).is_some() {
+        return;
     }
+    let saved = storage
+        .get_item(ANSWERS_KEY)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    storage
+        .set_item(
+            ANSWERS_KEY,
+            &saved
+                .lines()
+                .map(str::to_string)
+                .chain(
+                    answers.iter().map(|(position, cell)| {
+                        format!("{id} {} {} {cell}", position.x, position.y)
+                    }),
+                )
+                .join("\n"),
+        )
+        .unwrap();
+}
+
+/// The ids of every crossword released by `today`, newest first. Crosswords
+/// are embedded oldest first, so this is the reverse of their order.
+pub fn released(today: NaiveDate) -> impl Iterator<Item = usize> {
+    CROSSWORDS
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(move |(_, crossword)| crossword.date.is_none_or(|date| date <= today))
+        .map(|(id, _)| id)
 }
 
 lazy_static! {
     pub static ref CROSSWORDS: &'static [Crossword] = {
-        let data = String::from_utf8(
-            include_bytes!(concat!(env!("OUT_DIR"), "/crosswords"))
-                .bytes()
-                .map(Result::unwrap)
-                .map(u8::reverse_bits)
-                .collect(),
-        )
-        .unwrap()
-        .leak();
+        let data = include_str!(concat!(env!("OUT_DIR"), "/crosswords"));
         let crosswords: Vec<_> = data.split("\n\n").map(Crossword::from_str).collect();
         crosswords.leak()
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use import::{ipuz_to_crossword, puz_to_crossword};
+
+    const THEMED: &str = "\
+ice_cream 0 0 across Frozen treat
+ibis 0 0 down Bin chicken
+m[ilk]-shake 7 0 down Thick drink, with the cow's contribution in one square
+spoon 0 3 across Dessert utensil
+!circle 0 0
+!shade 7 0
+!bar 0 1 down
+!link 1 across 2 down
+!reveal Everything you'd find at a milk bar
+!date 2026-10-19";
+
+    const PLAIN: &str = "\
+surf 0 0 across Ride the waves
+sand 0 0 down It gets everywhere after a swim
+fetch 3 0 down What a dog does with a stick
+north 0 2 across Up on the map";
+
+    /// Builds a crossword from source lines the way the build script would,
+    /// with a fixed salt, along with its solution.
+    fn crossword(source: &str) -> (Crossword, HashMap<Vec2, String>) {
+        let sealed = source
+            .lines()
+            .map(|line| {
+                if line.starts_with('!') {
+                    return line.to_string();
+                }
+                let (answer, x, y, direction, clue) = line.splitn(5, ' ').collect_tuple().unwrap();
+                let hash = digest(
+                    "salt",
+                    &cells(answer).iter().map(String::as_str).collect_vec(),
+                );
+                format!("{answer} {x} {y} {direction} salt {hash} {clue}")
+            })
+            .join("\n");
+        let crossword = Crossword::from_str(sealed.leak());
+        let solution = crossword
+            .to_letters()
+            .into_iter()
+            .map(|letter| (letter.position, letter.cell.to_string()))
+            .collect();
+        (crossword, solution)
+    }
+
+    fn sorted(lines: &str) -> Vec<&str> {
+        lines.lines().sorted_unstable().collect()
+    }
+
+    #[test]
+    fn ipuz_round_trips() {
+        let (crossword, solution) = crossword(THEMED);
+        let ipuz = crossword.to_ipuz("Sundae", Some(&solution));
+        assert_eq!(sorted(&ipuz_to_crossword(&ipuz)), sorted(THEMED));
+    }
+
+    #[test]
+    fn ipuz_without_solution_has_no_answers() {
+        let (crossword, _) = crossword(THEMED);
+        let ipuz: Value = serde_json::from_str(&crossword.to_ipuz("Sundae", None)).unwrap();
+        assert!(ipuz.get("solution").is_none());
+        assert_eq!(ipuz["dimensions"], json!({ "width": 8, "height": 7 }));
+    }
+
+    #[test]
+    fn puz_round_trips() {
+        let (crossword, solution) = crossword(PLAIN);
+        let puz = crossword.to_puz("Crossword", Some(&solution));
+        assert_eq!(sorted(&puz_to_crossword(&puz)), sorted(PLAIN));
+    }
+
+    #[test]
+    fn puz_without_solution_is_marked() {
+        let (crossword, _) = crossword(PLAIN);
+        let puz = crossword.to_puz("Crossword", None);
+        assert_eq!(&puz[0x32..0x34], &[0x02, 0x00]);
+        assert_eq!(&puz[0x34..0x34 + 25], b"XXXX.X..X.XXXXXX..X....X.");
+    }
+
+    #[test]
+    fn puz_checksums_match_contents() {
+        let (crossword, solution) = crossword(PLAIN);
+        for puz in [
+            crossword.to_puz("Crossword", Some(&solution)),
+            crossword.to_puz("Crossword", None),
+        ] {
+            let word = |offset: usize| u16::from_le_bytes([puz[offset], puz[offset + 1]]);
+            let cells = usize::from(puz[0x2C]) * usize::from(puz[0x2D]);
+            let solution = &puz[0x34..0x34 + cells];
+            let state = &puz[0x34 + cells..0x34 + 2 * cells];
+            let strings = puz[0x34 + 2 * cells..]
+                .split(|byte| *byte == 0)
+                .collect_vec();
+            let clues = &strings[3..3 + usize::from(word(0x2E))];
+            let text = |initial| {
+                let initial = strings[..3].iter().fold(initial, |initial, string| {
+                    checksum(&[string, b"\0".as_slice()].concat(), initial)
+                });
+                clues
+                    .iter()
+                    .fold(initial, |initial, clue| checksum(clue, initial))
+            };
+
+            let cib = checksum(&puz[0x2C..0x34], 0);
+            assert_eq!(word(0x0E), cib);
+            assert_eq!(word(0x00), text(checksum(state, checksum(solution, cib))));
+            let masked = [cib, checksum(solution, 0), checksum(state, 0), text(0)];
+            for (index, checksum) in masked.into_iter().enumerate() {
+                let [low, high] = checksum.to_le_bytes();
+                assert_eq!(puz[0x10 + index], b"ICHE"[index] ^ low);
+                assert_eq!(puz[0x14 + index], b"ATED"[index] ^ high);
+            }
+        }
+    }
+
+    #[test]
+    fn sample_ipuz_round_trips() {
+        let source = ipuz_to_crossword(include_str!("crosswords/2.ipuz"));
+        let (crossword, solution) = crossword(&source);
+        let ipuz = crossword.to_ipuz("Day at the Beach", Some(&solution));
+        assert_eq!(sorted(&ipuz_to_crossword(&ipuz)), sorted(&source));
+    }
+
+    #[test]
+    fn sample_puz_is_exported_unchanged() {
+        let sample = include_bytes!("crosswords/3.puz");
+        let (crossword, solution) = crossword(&puz_to_crossword(sample));
+        assert_eq!(crossword.to_puz("Stargazing", Some(&solution)), sample);
+    }
+}
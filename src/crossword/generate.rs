@@ -0,0 +1,297 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::grid::{cells, Direction, Vec2, Word};
+
+/// Lays out answer/clue pairs into a connected grid where every word crosses
+/// another and no two words run alongside each other. Longer answers are placed
+/// first, and among valid spots the one with the most crossings and the smallest
+/// grid is tried first, backing off to the others when later words don't fit.
+/// The same entries and seed always give the same layout, since ChaCha's output
+/// is fixed across versions where `StdRng`'s isn't. Gives `None` if no layout
+/// turns up within [`BUDGET`] placements, which is always the case when some
+/// answer shares no letter with the rest.
+pub fn generate(entries: &[(&'static str, &'static str)], seed: u64) -> Option<Vec<Word>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut pending = entries
+        .iter()
+        .map(|(answer, clue)| {
+            let cells: Vec<_> = cells(answer)
+                .into_iter()
+                .map(|cell| &*cell.leak())
+                .collect();
+            Word {
+                answer,
+                cells: cells.leak(),
+                clue,
+                direction: Direction::Across,
+                position: Vec2::default(),
+            }
+        })
+        .collect::<Vec<_>>();
+    pending.shuffle(&mut rng);
+    pending.sort_by_key(|word| Reverse(word.length()));
+    // Words are placed around an origin far enough from zero that no placement
+    // can underflow, and the grid is moved back to the corner at the end.
+    let origin = pending.iter().map(Word::length).sum();
+    let first = *pending.first()?;
+    pending.remove(0);
+    let mut words = vec![Word {
+        direction: Direction::Across,
+        position: Vec2 {
+            x: origin,
+            y: origin,
+        },
+        ..first
+    }];
+    let mut budget = BUDGET;
+    if !place(&mut words, &mut pending, &mut rng, &mut budget) {
+        return None;
+    }
+    let corner = Vec2 {
+        x: words.iter().map(|word| word.position.x).min().unwrap(),
+        y: words.iter().map(|word| word.position.y).min().unwrap(),
+    };
+    for word in &mut words {
+        word.position -= corner;
+    }
+    Some(words)
+}
+
+/// Placements tried before giving up on a seed.
+const BUDGET: usize = 10_000;
+
+/// Places the first pending word that fits anywhere, trying its spots from best
+/// to worst until the rest can be placed around it.
+fn place(
+    words: &mut Vec<Word>,
+    pending: &mut Vec<Word>,
+    rng: &mut ChaCha8Rng,
+    budget: &mut usize,
+) -> bool {
+    if pending.is_empty() {
+        return true;
+    }
+    let letters = letters(words);
+    let Some((index, mut candidates)) = pending.iter().enumerate().find_map(|(index, word)| {
+        let mut candidates = candidates(*word, &letters);
+        candidates.shuffle(rng);
+        (!candidates.is_empty()).then_some((index, candidates))
+    }) else {
+        return false;
+    };
+    candidates.sort_by_key(|(crossings, word)| (*crossings, Reverse(area(words, word))));
+    let word = pending.remove(index);
+    for (_, candidate) in candidates.into_iter().rev() {
+        if *budget == 0 {
+            break;
+        }
+        *budget -= 1;
+        words.push(candidate);
+        if place(words, pending, rng, budget) {
+            return true;
+        }
+        words.pop();
+    }
+    pending.insert(index, word);
+    false
+}
+
+const fn step(direction: Direction) -> Vec2 {
+    match direction {
+        Direction::Across => Vec2 { x: 1, y: 0 },
+        Direction::Down => Vec2 { x: 0, y: 1 },
+    }
+}
+
+fn letters(words: &[Word]) -> HashMap<Vec2, (&'static str, Vec<Direction>)> {
+    let mut letters = HashMap::<_, (_, Vec<_>)>::new();
+    for word in words {
+        for (cell, position) in word.cells.iter().zip(word.positions()) {
+            letters
+                .entry(position)
+                .or_insert((*cell, Vec::new()))
+                .1
+                .push(word.direction);
+        }
+    }
+    letters
+}
+
+/// Every valid placement of `word` that crosses the existing grid, paired with
+/// the number of letters it shares.
+fn candidates(
+    word: Word,
+    letters: &HashMap<Vec2, (&'static str, Vec<Direction>)>,
+) -> Vec<(usize, Word)> {
+    let mut candidates = Vec::new();
+    for (position, (cell, directions)) in letters {
+        let [direction] = directions.as_slice() else {
+            continue;
+        };
+        let direction = direction.other();
+        for (index, _) in word
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| *other == cell)
+        {
+            let mut start = *position;
+            for _ in 0..index {
+                start -= step(direction);
+            }
+            let word = Word {
+                direction,
+                position: start,
+                ..word
+            };
+            if let Some(crossings) = crossings(&word, letters) {
+                candidates.push((crossings, word));
+            }
+        }
+    }
+    candidates.sort_by_key(|(_, word)| {
+        (
+            word.position.y,
+            word.position.x,
+            word.direction == Direction::Down,
+        )
+    });
+    candidates
+}
+
+/// Counts the letters `word` would share with the grid, or `None` if it would
+/// clash with a letter, extend another word, or sit beside a parallel word.
+fn crossings(
+    word: &Word,
+    letters: &HashMap<Vec2, (&'static str, Vec<Direction>)>,
+) -> Option<usize> {
+    let forward = step(word.direction);
+    let side = step(word.direction.other());
+    let end = word.positions().last()?;
+    if letters.contains_key(&(word.position - forward)) || letters.contains_key(&(end + forward)) {
+        return None;
+    }
+    let mut crossings = 0;
+    for (cell, position) in word.cells.iter().zip(word.positions()) {
+        match letters.get(&position) {
+            Some((existing, directions)) => {
+                if existing != cell || directions.contains(&word.direction) {
+                    return None;
+                }
+                crossings += 1;
+            }
+            None => {
+                if letters.contains_key(&(position - side))
+                    || letters.contains_key(&(position + side))
+                {
+                    return None;
+                }
+            }
+        }
+    }
+    (crossings > 0).then_some(crossings)
+}
+
+fn area(words: &[Word], word: &Word) -> usize {
+    let positions = || words.iter().chain([word]).flat_map(Word::positions);
+    let width = positions().map(|position| position.x).max().unwrap()
+        - positions().map(|position| position.x).min().unwrap();
+    let height = positions().map(|position| position.y).max().unwrap()
+        - positions().map(|position| position.y).min().unwrap();
+    (width + 1) * (height + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const ENTRIES: [(&str, &str); 7] = [
+        ("harbour", "Where the ferries run"),
+        ("opera", "House with sails"),
+        ("ferry", "Manly commuter's ride"),
+        ("bridge", "Coathanger"),
+        ("waratah", "State flower"),
+        ("jacaranda", "Purple bloomer in November"),
+        ("ibis", "Bin chicken"),
+    ];
+
+    /// The layouts of the first few seeds, all of which should find one.
+    fn layouts() -> impl Iterator<Item = Vec<Word>> {
+        (0..20).map(|seed| generate(&ENTRIES, seed).unwrap())
+    }
+
+    #[test]
+    fn letters_agree_where_words_cross() {
+        for words in layouts() {
+            let mut cells = HashMap::new();
+            for word in &words {
+                for (cell, position) in word.cells.iter().zip(word.positions()) {
+                    assert_eq!(*cells.entry(position).or_insert(cell), cell);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_word_is_connected() {
+        for words in layouts() {
+            assert_eq!(words.len(), ENTRIES.len());
+            let mut reached = HashSet::from([0]);
+            let mut queue = vec![0];
+            while let Some(index) = queue.pop() {
+                for (other, word) in words.iter().enumerate() {
+                    if words[index]
+                        .positions()
+                        .any(|position| word.contains(position))
+                        && reached.insert(other)
+                    {
+                        queue.push(other);
+                    }
+                }
+            }
+            assert_eq!(reached.len(), words.len());
+        }
+    }
+
+    #[test]
+    fn gives_up_on_an_unconnected_answer() {
+        let entries = [("cat", "Pet"), ("dog", "Pet"), ("owl", "Hooter")];
+        assert!((0..20).all(|seed| generate(&entries, seed).is_none()));
+    }
+
+    #[test]
+    fn seed_gives_a_fixed_layout() {
+        let layout = |seed| {
+            generate(&ENTRIES, seed)
+                .unwrap()
+                .iter()
+                .map(|word| {
+                    format!(
+                        "{} {} {} {}",
+                        word.answer, word.position.x, word.position.y, word.direction
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(layout(7), layout(7));
+        assert_eq!(
+            layout(7),
+            [
+                "jacaranda 1 1 Across",
+                "waratah 4 0 Down",
+                "harbour 3 3 Across",
+                "bridge 6 3 Down",
+                "ferry 5 8 Across",
+                "opera 0 5 Across",
+                "ibis 6 5 Across",
+            ]
+        );
+    }
+}
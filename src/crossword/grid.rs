@@ -0,0 +1,157 @@
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use strum::{Display, VariantArray};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Word {
+    /// The answer as written by the setter, with `_` between words, `-` in
+    /// hyphenated words and `[...]` around a rebus cell. In the app this is
+    /// only the pattern, with `?` in place of each cell.
+    pub answer: &'static str,
+    pub cells: &'static [&'static str],
+    pub clue: &'static str,
+    pub direction: Direction,
+    pub position: Vec2,
+}
+
+/// Splits an answer into the upper-cased contents of each cell, dropping word
+/// separators and keeping a bracketed rebus together in one cell.
+pub fn cells(answer: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut chars = answer.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '_' | '-' => {}
+            '[' => cells.push(
+                chars
+                    .by_ref()
+                    .take_while(|char| *char != ']')
+                    .collect::<String>()
+                    .to_uppercase(),
+            ),
+            char => cells.push(char.to_uppercase().collect()),
+        }
+    }
+    cells
+}
+
+impl Word {
+    pub const fn length(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The cell counts of each part of the answer, e.g. `(4,3)` for two words
+    /// or `(5-4)` for a hyphenated one.
+    pub fn enumeration(&self) -> String {
+        let mut enumeration = String::from("(");
+        let mut count = 0;
+        let mut in_rebus = false;
+        for char in self.answer.chars() {
+            match char {
+                '_' | '-' if !in_rebus => {
+                    enumeration += &format!("{count}{}", if char == '_' { ',' } else { '-' });
+                    count = 0;
+                }
+                '[' => {
+                    in_rebus = true;
+                    count += 1;
+                }
+                ']' => in_rebus = false,
+                _ if in_rebus => {}
+                _ => count += 1,
+            }
+        }
+        enumeration + &format!("{count})")
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Vec2> + '_ {
+        (0..self.length()).map(|index| match self.direction {
+            Direction::Across => Vec2 {
+                x: self.position.x + index,
+                y: self.position.y,
+            },
+            Direction::Down => Vec2 {
+                x: self.position.x,
+                y: self.position.y + index,
+            },
+        })
+    }
+
+    pub const fn contains(&self, position: Vec2) -> bool {
+        let end = match self.direction {
+            Direction::Across => Vec2 {
+                x: self.position.x + self.length() - 1,
+                y: self.position.y,
+            },
+            Direction::Down => Vec2 {
+                x: self.position.x,
+                y: self.position.y + self.length() - 1,
+            },
+        };
+        self.position.x <= position.x
+            && position.x <= end.x
+            && self.position.y <= position.y
+            && position.y <= end.y
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Letter {
+    pub cell: &'static str,
+    pub position: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, VariantArray, Display, Default)]
+pub enum Direction {
+    #[default]
+    Across,
+    Down,
+}
+
+impl Direction {
+    pub const ALL: &'static [Self] = Self::VARIANTS;
+    pub const fn other(self) -> Self {
+        match self {
+            Self::Across => Self::Down,
+            Self::Down => Self::Across,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Vec2 {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::iter::once;
+
+use chrono::NaiveDate;
+use itertools::Itertools;
+use serde_json::Value;
+
+/// A numbered clue, with the answer's length if the source gives it rather than
+/// leaving it to run to the next block, and its enumeration, e.g. `4,3`.
+pub struct Clue<'a> {
+    pub number: usize,
+    pub direction: &'a str,
+    pub text: String,
+    pub length: Option<usize>,
+    pub enumeration: Option<&'a str>,
+}
+
+/// Converts a solution grid (`None` for blocks) and its numbered clues into the
+/// `answer x y direction clue` lines used by `src/crosswords`. Answers run to
+/// the next block unless the clue gives an explicit length, cells holding more
+/// than one letter are written as a bracketed rebus, and an enumeration such as
+/// `4,3` puts the word breaks back into the answer.
+pub fn grid_to_crossword(
+    solution: &[Vec<Option<String>>],
+    numbers: &HashMap<usize, (usize, usize)>,
+    clues: &[Clue],
+) -> String {
+    clues
+        .iter()
+        .map(|clue| {
+            let (x, y) = numbers[&clue.number];
+            let length = clue.length.unwrap_or(usize::MAX);
+            let cells = match clue.direction {
+                "across" => solution[y][x..]
+                    .iter()
+                    .map_while(Option::as_deref)
+                    .take(length)
+                    .map(rebus)
+                    .collect_vec(),
+                "down" => solution[y..]
+                    .iter()
+                    .map_while(|row| row[x].as_deref())
+                    .take(length)
+                    .map(rebus)
+                    .collect_vec(),
+                _ => unreachable!(),
+            };
+            let answer = clue
+                .enumeration
+                .and_then(|enumeration| separate(&cells, enumeration))
+                .unwrap_or_else(|| cells.concat());
+            format!(
+                "{} {x} {y} {} {}",
+                answer.to_lowercase(),
+                clue.direction,
+                clue.text
+            )
+        })
+        .join("\n")
+}
+
+/// Joins an answer's cells with `_` between words and `-` in hyphenated words,
+/// or `None` if the enumeration doesn't add up to the answer's length.
+fn separate(cells: &[String], enumeration: &str) -> Option<String> {
+    let mut answer = String::new();
+    let mut rest = cells;
+    for part in enumeration.split_inclusive([',', ' ', '-']) {
+        let count = part.trim_matches(|char: char| !char.is_ascii_digit());
+        if count.is_empty() {
+            continue;
+        }
+        let count: usize = count.parse().ok()?;
+        answer += &rest.get(..count)?.concat();
+        rest = &rest[count..];
+        if !rest.is_empty() {
+            answer.push(if part.ends_with('-') { '-' } else { '_' });
+        }
+    }
+    rest.is_empty().then_some(answer)
+}
+
+fn rebus(cell: &str) -> String {
+    if cell.chars().count() > 1 {
+        format!("[{cell}]")
+    } else {
+        cell.to_string()
+    }
+}
+
+pub fn ipuz_to_crossword(data: &str) -> String {
+    let ipuz: Value = serde_json::from_str(data).unwrap();
+    let block = ipuz["block"].as_str().unwrap_or("#");
+    let number = |value: &Value| match value {
+        Value::Number(number) => number.as_u64().map(|number| number as usize),
+        Value::String(number) => number.parse().ok(),
+        Value::Object(cell) => cell
+            .get("cell")
+            .and_then(|cell| cell.as_u64())
+            .map(|number| number as usize),
+        _ => None,
+    };
+    let solution = ipuz["solution"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            row.as_array()
+                .unwrap()
+                .iter()
+                .map(|cell| match cell {
+                    Value::String(letter) if letter != block => Some(letter.clone()),
+                    Value::Object(cell) => cell
+                        .get("value")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    _ => None,
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+    let numbers = ipuz["puzzle"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .filter_map(move |(x, cell)| {
+                    number(cell)
+                        .filter(|number| *number != 0)
+                        .map(|number| (number, (x, y)))
+                })
+        })
+        .collect();
+    let clues = ipuz["clues"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(direction, clues)| {
+            let direction = match direction.split(':').next().unwrap() {
+                "Across" => "across",
+                "Down" => "down",
+                other => panic!("unsupported clue direction {other}"),
+            };
+            clues
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(move |clue| match clue {
+                    Value::Array(clue) => Clue {
+                        number: number(&clue[0]).unwrap(),
+                        direction,
+                        text: clue[1].as_str().unwrap().to_string(),
+                        length: None,
+                        enumeration: None,
+                    },
+                    Value::Object(clue) => Clue {
+                        number: number(&clue["number"]).unwrap(),
+                        direction,
+                        text: clue["clue"].as_str().unwrap().to_string(),
+                        length: clue.get("cells").and_then(Value::as_array).map(Vec::len),
+                        enumeration: clue.get("enumeration").and_then(Value::as_str),
+                    },
+                    _ => unreachable!(),
+                })
+        })
+        .collect_vec();
+    let styles = ipuz["puzzle"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .filter_map(move |(x, cell)| cell.get("style").map(|style| (x, y, style)))
+        })
+        .flat_map(|(x, y, style)| {
+            let mut lines = Vec::new();
+            if style["shapebg"] == "circle" {
+                lines.push(format!("!circle {x} {y}"));
+            }
+            if style["highlight"] == true || style.get("color").is_some() {
+                lines.push(format!("!shade {x} {y}"));
+            }
+            for side in style["barred"].as_str().unwrap_or_default().chars() {
+                match side {
+                    'R' => lines.push(format!("!bar {x} {y} across")),
+                    'B' => lines.push(format!("!bar {x} {y} down")),
+                    'L' if x > 0 => lines.push(format!("!bar {} {y} across", x - 1)),
+                    'T' if y > 0 => lines.push(format!("!bar {x} {} down", y - 1)),
+                    _ => {}
+                }
+            }
+            lines
+        });
+    let links = ipuz["clues"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(direction, clues)| {
+            clues.as_array().unwrap().iter().filter_map(move |clue| {
+                let continued = clue.get("continued")?.as_array()?;
+                Some(
+                    once(format!(
+                        "!link {} {}",
+                        number(&clue["number"]).unwrap(),
+                        direction.split(':').next().unwrap()
+                    ))
+                    .chain(continued.iter().map(|reference| {
+                        format!(
+                            "{} {}",
+                            number(&reference["number"]).unwrap(),
+                            reference["direction"].as_str().unwrap()
+                        )
+                    }))
+                    .join(" ")
+                    .to_lowercase(),
+                )
+            })
+        });
+    let reveal = ipuz["explanation"]
+        .as_str()
+        .map(|explanation| format!("!reveal {explanation}"));
+    let date = ipuz["date"].as_str().map(|date| {
+        let date = NaiveDate::parse_from_str(date, "%m/%d/%Y").unwrap();
+        format!("!date {}", date.format("%Y-%m-%d"))
+    });
+    once(grid_to_crossword(&solution, &numbers, &clues))
+        .chain(styles)
+        .chain(links)
+        .chain(reveal)
+        .chain(date)
+        .join("\n")
+}
+
+/// Reads an Across Lite file, which stores the solution as one byte per cell
+/// (`.` for blocks) followed by NUL-terminated title, author, copyright and
+/// clue strings. Clues are implicitly numbered in grid order, across first.
+pub fn puz_to_crossword(data: &[u8]) -> String {
+    const BLACK: u8 = b'.';
+    assert_eq!(
+        &data[0x02..0x0E],
+        b"ACROSS&DOWN\0",
+        "not an Across Lite file"
+    );
+    assert_eq!(
+        u16::from_le_bytes([data[0x32], data[0x33]]) & 0x0006,
+        0,
+        "the Across Lite file's solution is missing or scrambled"
+    );
+    let width = usize::from(data[0x2C]);
+    let height = usize::from(data[0x2D]);
+    let solution = data[0x34..0x34 + width * height]
+        .chunks(width)
+        .map(|row| {
+            row.iter()
+                .map(|cell| (*cell != BLACK).then(|| char::from(*cell).to_string()))
+                .collect_vec()
+        })
+        .collect_vec();
+    let mut strings = data[0x34 + 2 * width * height..]
+        .split(|byte| *byte == 0)
+        .map(|string| string.iter().copied().map(char::from).collect::<String>())
+        .skip(3);
+    let filled = |x: usize, y: usize| x < width && y < height && solution[y][x].is_some();
+    let mut numbers = HashMap::new();
+    let mut clues = Vec::new();
+    for (y, x) in (0..height).cartesian_product(0..width) {
+        let across = filled(x, y) && (x == 0 || !filled(x - 1, y)) && filled(x + 1, y);
+        let down = filled(x, y) && (y == 0 || !filled(x, y - 1)) && filled(x, y + 1);
+        if !(across || down) {
+            continue;
+        }
+        let number = numbers.len() + 1;
+        numbers.insert(number, (x, y));
+        for (starts, direction) in [(across, "across"), (down, "down")] {
+            if starts {
+                clues.push(Clue {
+                    number,
+                    direction,
+                    text: strings.next().unwrap(),
+                    length: None,
+                    enumeration: None,
+                });
+            }
+        }
+    }
+    grid_to_crossword(&solution, &numbers, &clues)
+}
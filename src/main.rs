@@ -3,12 +3,25 @@ use console_error_panic_hook::set_once;
 use leptos::{mount_to_body, view};
 
 mod ad;
+mod analytics;
 mod article;
 #[allow(non_snake_case)]
 mod components;
+mod consent;
 mod crossword;
+mod offline;
+mod picture;
+mod puzzle;
+mod quiz;
+mod speech;
+mod stats;
+mod storage;
+mod theme;
+mod typography;
 
 fn main() {
     set_once();
+    ad::report::start();
+    offline::register();
     mount_to_body(move || view! { <App /> });
 }
@@ -0,0 +1,50 @@
+use std::iter::once;
+
+use leptos::wasm_bindgen::JsValue;
+use leptos::web_sys::js_sys::{Array, Reflect};
+use leptos::window;
+
+use crate::article::{Article, Fragment};
+use crate::picture;
+
+fn supported() -> bool {
+    Reflect::has(&window().navigator(), &JsValue::from_str("serviceWorker")).unwrap_or_default()
+}
+
+/// Installs `src/sw.js`, which keeps the site and recently opened articles for
+/// reading offline.
+pub fn register() {
+    if supported() {
+        _ = window().navigator().service_worker().register("/sw.js");
+    }
+}
+
+/// Asks the service worker to keep an article's images, including any further
+/// down the page that haven't loaded yet. Resized images are sent as their
+/// directory, so whichever copy the browser picks is kept, along with the
+/// largest copy to fetch now.
+pub fn keep(article: &Article) {
+    if !supported() {
+        return;
+    }
+    let Some(worker) = window().navigator().service_worker().controller() else {
+        return;
+    };
+    let urls: Array = once(article.image.url)
+        .chain(
+            article
+                .fragments
+                .iter()
+                .filter_map(|fragment| match fragment {
+                    Fragment::Image(image) => Some(image.url),
+                    Fragment::Text(_) => None,
+                }),
+        )
+        .flat_map(|url| match picture::variants(url) {
+            Some(variants) => vec![variants.directory(), variants.fallback()],
+            None => vec![url.to_string()],
+        })
+        .map(|url| JsValue::from_str(&url))
+        .collect();
+    _ = worker.post_message(&urls);
+}
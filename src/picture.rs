@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+/// The resized copies of an article image in `src/images/cache`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variants {
+    stem: &'static str,
+    pub width: u32,
+    pub height: u32,
+    /// PNG for images with transparency and JPEG otherwise, for browsers without
+    /// WebP.
+    extension: &'static str,
+    widths: Vec<u32>,
+    formats: Vec<&'static str>,
+    /// A tiny copy as a `data:` URL, shown blurred while the image loads.
+    pub placeholder: &'static str,
+}
+
+impl Variants {
+    /// Parses a `stem width height extension widths formats placeholder url`
+    /// line, returning the URL alongside.
+    fn from_str(s: &'static str) -> (&'static str, Self) {
+        let (stem, width, height, extension, widths, formats, placeholder, url) =
+            s.splitn(8, ' ').collect_tuple().unwrap();
+        (
+            url,
+            Self {
+                stem,
+                width: width.parse().unwrap(),
+                height: height.parse().unwrap(),
+                extension,
+                widths: widths
+                    .split(',')
+                    .map(|width| width.parse().unwrap())
+                    .collect(),
+                formats: formats.split(',').collect(),
+                placeholder,
+            },
+        )
+    }
+
+    fn url(&self, width: u32, format: &str) -> String {
+        format!("{}{width}.{format}", self.directory())
+    }
+
+    /// Where every copy is kept, which the service worker treats as one image.
+    pub fn directory(&self) -> String {
+        format!("/images/cache/{}/", self.stem)
+    }
+
+    /// The `srcset` for one format, or `None` if it wasn't generated.
+    pub fn srcset(&self, format: &str) -> Option<String> {
+        self.formats.contains(&format).then(|| {
+            self.widths
+                .iter()
+                .map(|width| format!("{} {width}w", self.url(*width, format)))
+                .join(", ")
+        })
+    }
+
+    pub fn fallback_srcset(&self) -> String {
+        self.srcset(self.extension).unwrap()
+    }
+
+    /// The largest copy, for browsers that ignore `srcset`.
+    pub fn fallback(&self) -> String {
+        self.url(*self.widths.last().unwrap(), self.extension)
+    }
+}
+
+pub fn variants(url: &str) -> Option<&'static Variants> {
+    VARIANTS.get(url)
+}
+
+lazy_static! {
+    static ref VARIANTS: HashMap<&'static str, Variants> = {
+        let data = include_str!(concat!(env!("OUT_DIR"), "/images"));
+        data.lines().map(Variants::from_str).collect()
+    };
+}
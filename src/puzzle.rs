@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use strum::{Display, EnumString, VariantArray};
+
+mod headline;
+mod sudoku;
+mod word_search;
+
+pub use headline::Headline;
+pub use sudoku::Sudoku;
+pub use word_search::WordSearch;
+
+/// The kinds of puzzle, named as their `src/puzzles` directory and route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "lowercase")]
+pub enum Kind {
+    WordSearch,
+    Headline,
+    Sudoku,
+}
+
+impl Kind {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::WordSearch => "Word search",
+            Self::Headline => "Guess the headline",
+            Self::Sudoku => "Mini sudoku",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Puzzle {
+    WordSearch(WordSearch),
+    Headline(Headline),
+    Sudoku(Sudoku),
+}
+
+impl Puzzle {
+    fn from_str(kind: Kind, s: &'static str) -> Self {
+        match kind {
+            Kind::WordSearch => Self::WordSearch(WordSearch::from_str(s)),
+            Kind::Headline => Self::Headline(Headline::from_str(s)),
+            Kind::Sudoku => Self::Sudoku(Sudoku::from_str(s)),
+        }
+    }
+
+    pub const fn kind(&self) -> Kind {
+        match self {
+            Self::WordSearch(_) => Kind::WordSearch,
+            Self::Headline(_) => Kind::Headline,
+            Self::Sudoku(_) => Kind::Sudoku,
+        }
+    }
+}
+
+pub fn find(kind: Kind, id: &str) -> Option<&'static Puzzle> {
+    PUZZLES
+        .iter()
+        .find(|(other_id, puzzle)| puzzle.kind() == kind && *other_id == id)
+        .map(|(_, puzzle)| puzzle)
+}
+
+lazy_static! {
+    /// Every puzzle with its id, read from length-prefixed `kind id` records.
+    pub static ref PUZZLES: &'static [(&'static str, Puzzle)] = {
+        let mut data = include_str!(concat!(env!("OUT_DIR"), "/puzzles"));
+        let mut puzzles = Vec::new();
+        while !data.is_empty() {
+            let (length, rest) = data.split_once(' ').unwrap();
+            let length: usize = length.parse().unwrap();
+            let (header, body) = rest[..length].split_once('\n').unwrap();
+            let (kind, id) = header.split_once(' ').unwrap();
+            puzzles.push((id, Puzzle::from_str(Kind::from_str(kind).unwrap(), body)));
+            data = rest.get(length + 1..).unwrap_or_default();
+        }
+        puzzles.leak()
+    };
+}
@@ -0,0 +1,52 @@
+use crate::article::{Article, ARTICLES};
+
+/// An article's title with some of its words hidden for the reader to guess.
+#[derive(Debug, Clone)]
+pub struct Headline {
+    pub article: &'static Article,
+    /// Indices of the hidden words in the title.
+    pub hidden: Vec<usize>,
+}
+
+impl Headline {
+    /// Parses the id of an article and a line of the words in its title to hide.
+    pub fn from_str(s: &'static str) -> Self {
+        let mut lines = s.trim().lines();
+        let id = lines.next().unwrap().trim();
+        let article = ARTICLES.iter().find(|article| article.id == id).unwrap();
+        let hidden_words = lines.next().unwrap_or_default();
+        let hidden = Self::words_of(article.title)
+            .enumerate()
+            .filter(|(_, word)| {
+                hidden_words
+                    .split_whitespace()
+                    .any(|hidden| Self::normalise(hidden) == Self::normalise(word))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        Self { article, hidden }
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &'static str> {
+        Self::words_of(self.article.title)
+    }
+
+    fn words_of(title: &'static str) -> impl Iterator<Item = &'static str> {
+        title.split_whitespace()
+    }
+
+    /// Whether a guess matches the word at `index`, ignoring case and
+    /// punctuation.
+    pub fn is_correct(&self, index: usize, guess: &str) -> bool {
+        self.words()
+            .nth(index)
+            .is_some_and(|word| Self::normalise(word) == Self::normalise(guess))
+    }
+
+    fn normalise(word: &str) -> String {
+        word.chars()
+            .filter(|char| char.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+}
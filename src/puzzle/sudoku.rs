@@ -0,0 +1,87 @@
+use itertools::Itertools;
+
+use crate::crossword::Vec2;
+
+/// A small sudoku, with 4, 6 or 9 digits per row.
+#[derive(Debug, Clone)]
+pub struct Sudoku {
+    /// The givens in row-major order, with `None` for cells to fill in.
+    pub givens: Vec<Option<u8>>,
+    pub size: usize,
+    /// The width and height of each box.
+    pub region: Vec2,
+}
+
+impl Sudoku {
+    /// Parses one line per row of digits, with `.` for an empty cell.
+    pub fn from_str(s: &'static str) -> Self {
+        let rows = s
+            .trim()
+            .lines()
+            .map(|line| {
+                line.trim()
+                    .chars()
+                    .map(|char| char.to_digit(10).map(|digit| u8::try_from(digit).unwrap()))
+                    .collect_vec()
+            })
+            .collect_vec();
+        let size = rows.len();
+        let region = match size {
+            4 => Vec2 { x: 2, y: 2 },
+            6 => Vec2 { x: 3, y: 2 },
+            9 => Vec2 { x: 3, y: 3 },
+            _ => unreachable!("unsupported sudoku size {size}"),
+        };
+        Self {
+            givens: rows.concat(),
+            size,
+            region,
+        }
+    }
+
+    /// Whether `digits`, a full grid in row-major order, follows the rules and
+    /// keeps every given.
+    pub fn is_solved(&self, digits: &[Option<u8>]) -> bool {
+        let size = self.size;
+        let complete = |cells: Vec<usize>| {
+            cells
+                .iter()
+                .filter_map(|index| digits[*index])
+                .filter(|digit| (1..=size).contains(&usize::from(*digit)))
+                .unique()
+                .count()
+                == size
+        };
+        let keeps_givens = self
+            .givens
+            .iter()
+            .zip(digits)
+            .all(|(given, digit)| given.is_none() || given == digit);
+        keeps_givens
+            && (0..size).all(|row| complete((0..size).map(|x| row * size + x).collect()))
+            && (0..size).all(|column| complete((0..size).map(|y| y * size + column).collect()))
+            && (0..size).all(|region| {
+                let corner = Vec2 {
+                    x: region % (size / self.region.x) * self.region.x,
+                    y: region / (size / self.region.x) * self.region.y,
+                };
+                complete(
+                    (0..self.region.y)
+                        .cartesian_product(0..self.region.x)
+                        .map(|(y, x)| (corner.y + y) * size + corner.x + x)
+                        .collect(),
+                )
+            })
+    }
+
+    /// Whether the cell at `index` sits on the right or bottom edge of a box,
+    /// for drawing thicker borders.
+    pub fn edges(&self, index: usize) -> (bool, bool) {
+        let x = index % self.size;
+        let y = index / self.size;
+        (
+            (x + 1).is_multiple_of(self.region.x) && x + 1 < self.size,
+            (y + 1).is_multiple_of(self.region.y) && y + 1 < self.size,
+        )
+    }
+}
@@ -0,0 +1,72 @@
+use itertools::Itertools;
+
+use crate::crossword::Vec2;
+
+/// A grid of letters hiding words that run in any of the eight directions.
+#[derive(Debug, Clone)]
+pub struct WordSearch {
+    pub title: &'static str,
+    pub grid: Vec<Vec<char>>,
+    pub words: Vec<&'static str>,
+}
+
+impl WordSearch {
+    /// Parses a title line, the rows of the grid, a blank line and then one
+    /// hidden word per line.
+    pub fn from_str(s: &'static str) -> Self {
+        let mut lines = s.trim().lines();
+        let title = lines.next().unwrap();
+        let grid = lines
+            .by_ref()
+            .take_while(|line| !line.trim().is_empty())
+            .map(|line| line.trim().to_uppercase().chars().collect_vec())
+            .collect_vec();
+        let words = lines
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        Self { title, grid, words }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        Vec2 {
+            x: self.grid.first().map_or(0, Vec::len),
+            y: self.grid.len(),
+        }
+    }
+
+    /// The cells in a straight line from `start` to `end`, or `None` if they
+    /// aren't in the same row, column or diagonal.
+    pub fn line(start: Vec2, end: Vec2) -> Option<Vec<Vec2>> {
+        let dx = end.x.abs_diff(start.x);
+        let dy = end.y.abs_diff(start.y);
+        if !(dx == 0 || dy == 0 || dx == dy) {
+            return None;
+        }
+        let step = |from: usize, to: usize, index: usize| {
+            if to >= from {
+                from + index.min(to - from)
+            } else {
+                from - index.min(from - to)
+            }
+        };
+        Some(
+            (0..=dx.max(dy))
+                .map(|index| Vec2 {
+                    x: step(start.x, end.x, index),
+                    y: step(start.y, end.y, index),
+                })
+                .collect(),
+        )
+    }
+
+    /// The hidden word spelled out by a line of cells, read either way.
+    pub fn word_at(&self, cells: &[Vec2]) -> Option<&'static str> {
+        let letters: String = cells.iter().map(|cell| self.grid[cell.y][cell.x]).collect();
+        let reversed: String = letters.chars().rev().collect();
+        self.words.iter().copied().find(|word| {
+            let word = word.to_uppercase().replace(' ', "");
+            word == letters || word == reversed
+        })
+    }
+}
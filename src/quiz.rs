@@ -0,0 +1,91 @@
+use itertools::Itertools;
+use lazy_static::lazy_static;
+
+use crate::article::{Article, ARTICLES};
+use crate::storage::storage;
+
+const KEY: &str = "quiz-scores";
+
+#[derive(Debug, Clone)]
+pub struct Quiz {
+    /// The date the quiz was published, e.g. `2024-06-03`.
+    pub week: &'static str,
+    pub questions: Vec<Question>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Question {
+    pub article: &'static Article,
+    pub text: &'static str,
+    pub answers: Vec<&'static str>,
+    pub correct: usize,
+}
+
+impl Quiz {
+    /// Parses the week on the first line, then blocks of an article id, the
+    /// question and its answers, with `*` before the correct one.
+    fn from_str(s: &'static str) -> Self {
+        let (week, questions) = s.split_once('\n').unwrap();
+        Self {
+            week,
+            questions: questions
+                .trim()
+                .split("\n\n")
+                .map(|block| {
+                    let mut lines = block.lines().map(str::trim);
+                    let id = lines.next().unwrap();
+                    let text = lines.next().unwrap();
+                    let (correct, answers): (Vec<_>, Vec<_>) = lines
+                        .enumerate()
+                        .map(|(index, answer)| match answer.strip_prefix('*') {
+                            Some(answer) => (Some(index), answer),
+                            None => (None, answer),
+                        })
+                        .unzip();
+                    Question {
+                        article: ARTICLES.iter().find(|article| article.id == id).unwrap(),
+                        text,
+                        answers,
+                        correct: correct.into_iter().flatten().exactly_one().unwrap(),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The reader's score for a week's quiz, if they've finished it.
+pub fn score(week: &str) -> Option<usize> {
+    storage()?.get_item(KEY).ok()??.lines().find_map(|line| {
+        let (other, score) = line.split_once(' ')?;
+        (other == week).then(|| score.parse().ok()).flatten()
+    })
+}
+
+/// Keeps the reader's first score for a week's quiz in `localStorage` as
+/// `week score` lines.
+pub fn record(week: &str, score: usize) {
+    let Some(storage) = storage() else {
+        return;
+    };
+    if self::score(week).is_some() {
+        return;
+    }
+    let scores = storage.get_item(KEY).ok().flatten().unwrap_or_default();
+    storage
+        .set_item(
+            KEY,
+            &scores
+                .lines()
+                .chain([format!("{week} {score}").as_str()])
+                .join("\n"),
+        )
+        .unwrap();
+}
+
+lazy_static! {
+    pub static ref QUIZ: Option<Quiz> = {
+        let data = include_str!(concat!(env!("OUT_DIR"), "/quiz"));
+        (!data.is_empty()).then(|| Quiz::from_str(data))
+    };
+}
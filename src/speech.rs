@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use itertools::Itertools;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::{JsCast, JsValue};
+use leptos::web_sys::js_sys::Reflect;
+use leptos::web_sys::{SpeechSynthesis, SpeechSynthesisUtterance};
+use leptos::window;
+
+use crate::article::{Article, Fragment};
+
+/// Bumped whenever speech is started or stopped, so events from utterances
+/// that were cancelled are ignored.
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+fn synthesis() -> Option<SpeechSynthesis> {
+    Reflect::has(&window(), &JsValue::from_str("speechSynthesis"))
+        .unwrap_or_default()
+        .then(|| window().speech_synthesis().ok())
+        .flatten()
+}
+
+pub fn supported() -> bool {
+    synthesis().is_some()
+}
+
+/// What to read aloud for an article, in order, with the index of the
+/// fragment each part comes from.
+pub fn script(article: &Article, captions: bool) -> Vec<(Option<usize>, String)> {
+    [
+        (None, article.title.to_string()),
+        (None, article.blurb.to_string()),
+    ]
+    .into_iter()
+    .chain(
+        article
+            .fragments
+            .iter()
+            .enumerate()
+            .filter_map(|(index, fragment)| match fragment {
+                Fragment::Text(text) => Some((Some(index), (*text).to_string())),
+                Fragment::Image(image) => {
+                    captions.then(|| (Some(index), format!("Image: {}", image.caption)))
+                }
+            }),
+    )
+    .collect()
+}
+
+/// Reads `parts` aloud from `start`, calling `on_part` with the position of
+/// each part as it begins and `on_end` once the last has finished.
+pub fn speak(
+    parts: &[(Option<usize>, String)],
+    start: usize,
+    rate: f32,
+    on_part: impl Fn(usize) + Clone + 'static,
+    on_end: impl Fn() + 'static,
+) {
+    let Some(synthesis) = synthesis() else {
+        return;
+    };
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    synthesis.cancel();
+    let current = move || GENERATION.load(Ordering::Relaxed) == generation;
+    let utterances = parts
+        .iter()
+        .enumerate()
+        .skip(start)
+        .map(|(position, (_, text))| {
+            let utterance = SpeechSynthesisUtterance::new_with_text(text).unwrap();
+            utterance.set_lang("en-AU");
+            utterance.set_rate(rate);
+            let on_part = on_part.clone();
+            let on_start = Closure::<dyn Fn()>::new(move || {
+                if current() {
+                    on_part(position);
+                }
+            });
+            utterance.set_onstart(Some(on_start.into_js_value().unchecked_ref()));
+            utterance
+        })
+        .collect_vec();
+    if let Some(last) = utterances.last() {
+        let on_end = Closure::<dyn Fn()>::new(move || {
+            if current() {
+                on_end();
+            }
+        });
+        last.set_onend(Some(on_end.into_js_value().unchecked_ref()));
+    }
+    for utterance in &utterances {
+        synthesis.speak(utterance);
+    }
+}
+
+pub fn pause() {
+    if let Some(synthesis) = synthesis() {
+        synthesis.pause();
+    }
+}
+
+pub fn resume() {
+    if let Some(synthesis) = synthesis() {
+        synthesis.resume();
+    }
+}
+
+pub fn stop() {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+    if let Some(synthesis) = synthesis() {
+        synthesis.cancel();
+    }
+}
@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+use chrono::{Days, NaiveDate};
+use itertools::Itertools;
+
+use crate::storage::storage;
+
+const KEY: &str = "crossword-solves";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solve {
+    pub id: usize,
+    pub date: NaiveDate,
+    pub seconds: i64,
+}
+
+/// Every crossword the reader has completed, stored in `localStorage` as
+/// `id date seconds` lines.
+pub fn solves() -> Vec<Solve> {
+    storage()
+        .and_then(|storage| storage.get_item(KEY).ok().flatten())
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (id, date, seconds) = line.split(' ').collect_tuple()?;
+            Some(Solve {
+                id: usize::from_str(id).ok()?,
+                date: NaiveDate::from_str(date).ok()?,
+                seconds: i64::from_str(seconds).ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Records a completed crossword, keeping only the first time each one is
+/// solved.
+pub fn record(solve: Solve) {
+    let Some(storage) = storage() else {
+        return;
+    };
+    let mut solves = solves();
+    if solves.iter().any(|other| other.id == solve.id) {
+        return;
+    }
+    solves.push(solve);
+    storage
+        .set_item(
+            KEY,
+            &solves
+                .iter()
+                .map(|solve| format!("{} {} {}", solve.id, solve.date, solve.seconds))
+                .join("\n"),
+        )
+        .unwrap();
+}
+
+/// The current and longest runs of consecutive days with a solve. The current
+/// run still counts if today's crossword hasn't been done yet.
+pub fn streaks(solves: &[Solve], today: NaiveDate) -> (usize, usize) {
+    let days = solves
+        .iter()
+        .map(|solve| solve.date)
+        .sorted_unstable()
+        .dedup()
+        .collect_vec();
+    let mut longest = 0;
+    let mut run = 0;
+    for (index, day) in days.iter().enumerate() {
+        run = if index > 0 && days[index - 1].checked_add_days(Days::new(1)) == Some(*day) {
+            run + 1
+        } else {
+            1
+        };
+        longest = longest.max(run);
+    }
+    let yesterday = today.checked_sub_days(Days::new(1));
+    let current = match days.last() {
+        Some(last) if *last == today || Some(*last) == yesterday => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+/// Counts solves by whole minutes taken, with everything past the last bucket
+/// counted in it.
+pub fn histogram(solves: &[Solve], buckets: usize) -> Vec<usize> {
+    let mut histogram = vec![0; buckets];
+    for solve in solves {
+        let minutes = usize::try_from(solve.seconds / 60).unwrap_or_default();
+        histogram[minutes.min(buckets - 1)] += 1;
+    }
+    histogram
+}
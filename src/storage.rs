@@ -0,0 +1,8 @@
+use leptos::web_sys::Storage;
+use leptos::window;
+
+/// The browser's `localStorage`, or `None` where it's unavailable, such as in
+/// some private browsing modes.
+pub fn storage() -> Option<Storage> {
+    window().local_storage().ok().flatten()
+}
@@ -0,0 +1,187 @@
+// Keeps the app shell, fonts and the articles a reader has opened so the
+// site works offline. The articles themselves are compiled into the app, so
+// caching the shell caches the whole article index. Images are only kept for
+// articles the app asks for through `offline::keep`, and pages that were never
+// visited get `/offline.html`.
+
+const VERSION = "v2";
+const SHELL = `shell-${VERSION}`;
+const IMAGES = `images-${VERSION}`;
+// The image URLs, or `/`-terminated directories of resized copies, that
+// belong to kept articles.
+const KEPT = `kept-${VERSION}`;
+// Images kept from recently opened articles before the oldest are dropped.
+const MAX_IMAGES = 60;
+
+const PRECACHE = [
+    "/",
+    "/offline.html",
+    "/manifest.webmanifest",
+    "/images/favicon.ico",
+    "/images/icon-128.png",
+    "/images/icon-256.png",
+    "/images/icon-192.png",
+    "/images/icon-512.png",
+    "/images/icon-maskable-192.png",
+    "/images/icon-maskable-512.png",
+    "/images/missing.svg",
+    "/fonts/NotoSansDisplay.ttf",
+    "/fonts/PTSans.ttf",
+    "/fonts/PTSerif.ttf",
+    "/fonts/UnifrakturMaguntia.ttf",
+];
+
+// Trunk gives the script, wasm and stylesheet hashed names, so they're found
+// in the page it serves.
+async function precache() {
+    const cache = await caches.open(SHELL);
+    await cache.addAll(PRECACHE);
+    const index = await (await cache.match("/")).text();
+    const assets = [...index.matchAll(/(?:href|src)="(\/[^"]+\.(?:js|wasm|css))"/g)]
+        .map((match) => match[1]);
+    await cache.addAll(assets);
+}
+
+async function trim(cache, max) {
+    const keys = await cache.keys();
+    await Promise.all(keys.slice(0, Math.max(keys.length - max, 0)).map((key) => cache.delete(key)));
+}
+
+// Opaque responses can't be checked, and an error page cached as an image
+// would be served until it's trimmed.
+function cacheable(response) {
+    return response.ok && (response.type === "basic" || response.type === "cors");
+}
+
+async function keep(urls) {
+    const articles = await caches.open(KEPT);
+    await Promise.all(urls.map((url) => articles.put(url, new Response())));
+    await trim(articles, MAX_IMAGES);
+    const cache = await caches.open(IMAGES);
+    for (const url of urls.filter((url) => !url.endsWith("/"))) {
+        if (await cache.match(url)) {
+            continue;
+        }
+        try {
+            const response = await fetch(url, { mode: "cors" });
+            if (cacheable(response)) {
+                await cache.put(url, response);
+            }
+        } catch {
+            // Left for the next time the article is opened.
+        }
+    }
+    await trim(cache, MAX_IMAGES);
+}
+
+// The directory of resized copies an image belongs to, if it's one of them.
+function directory(url) {
+    const { origin, pathname } = new URL(url);
+    const match = origin === self.location.origin && pathname.match(/^\/images\/cache\/[^/]+\//);
+    return match && new URL(match[0], self.location.origin).href;
+}
+
+async function kept(url) {
+    const articles = await caches.open(KEPT);
+    const dir = directory(url);
+    return Boolean((await articles.match(url)) ?? (dir && (await articles.match(dir))));
+}
+
+// Every route is the same single page app, so any page the reader has visited
+// comes from the network when possible and the cache otherwise.
+async function navigate(request) {
+    try {
+        const response = await fetch(request);
+        const page = response.headers.get("content-type")?.startsWith("text/html");
+        if (response.ok && page && new URL(request.url).pathname !== "/offline.html") {
+            const cache = await caches.open(SHELL);
+            await cache.put(request, response.clone());
+        }
+        return response;
+    } catch {
+        return (await caches.match(request)) ?? (await caches.match("/offline.html"));
+    }
+}
+
+// Offline, another width or format of the same image is better than none.
+async function variant(url) {
+    const dir = directory(url);
+    if (!dir) {
+        return undefined;
+    }
+    const cache = await caches.open(IMAGES);
+    const key = (await cache.keys()).find((key) => key.url.startsWith(dir));
+    return key && cache.match(key);
+}
+
+async function image(request) {
+    const cached = await caches.match(request);
+    if (cached) {
+        return cached;
+    }
+    let response;
+    try {
+        response = await fetch(request);
+    } catch (error) {
+        const fallback = await variant(request.url);
+        if (fallback) {
+            return fallback;
+        }
+        throw error;
+    }
+    if (cacheable(response) && (await kept(request.url))) {
+        const cache = await caches.open(IMAGES);
+        await cache.put(request, response.clone());
+        await trim(cache, MAX_IMAGES);
+    }
+    return response;
+}
+
+async function asset(request) {
+    const cached = await caches.match(request);
+    if (cached) {
+        return cached;
+    }
+    const response = await fetch(request);
+    if (response.ok) {
+        const cache = await caches.open(SHELL);
+        await cache.put(request, response.clone());
+    }
+    return response;
+}
+
+self.addEventListener("install", (event) => {
+    event.waitUntil(precache().then(() => self.skipWaiting()));
+});
+
+self.addEventListener("activate", (event) => {
+    event.waitUntil(
+        caches.keys()
+            .then((keys) => Promise.all(
+                keys.filter((key) => ![SHELL, IMAGES, KEPT].includes(key)).map((key) => caches.delete(key)),
+            ))
+            .then(() => self.clients.claim()),
+    );
+});
+
+// The app sends the image URLs of each article as it's opened.
+self.addEventListener("message", (event) => {
+    if (Array.isArray(event.data)) {
+        event.waitUntil(keep(event.data));
+    }
+});
+
+self.addEventListener("fetch", (event) => {
+    const request = event.request;
+    const url = new URL(request.url);
+    if (request.method !== "GET") {
+        return;
+    }
+    if (request.mode === "navigate") {
+        event.respondWith(navigate(request));
+    } else if (request.destination === "image") {
+        event.respondWith(image(request));
+    } else if (url.origin === self.location.origin) {
+        event.respondWith(asset(request));
+    }
+});
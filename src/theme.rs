@@ -0,0 +1,70 @@
+use leptos::{document, window};
+use strum::{Display, EnumString, VariantArray};
+
+use crate::storage::storage;
+
+/// The reader's theme, also read by the script in `index.html` so the page
+/// doesn't flash the wrong colours while loading.
+const KEY: &str = "theme";
+
+/// A colour scheme, set as `data-theme` on the root element for the colours
+/// in `src/styles/input.css`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "lowercase")]
+pub enum Theme {
+    /// Light or dark, following `prefers-color-scheme`.
+    #[default]
+    System,
+    Light,
+    Dark,
+    Sepia,
+}
+
+impl Theme {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::System => "Automatic",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+            Self::Sepia => "Sepia",
+        }
+    }
+}
+
+pub fn saved() -> Theme {
+    storage()
+        .and_then(|storage| storage.get_item(KEY).ok().flatten())
+        .and_then(|theme| theme.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Sets a theme on the root element, resolving [`Theme::System`] to light or
+/// dark as the script in `index.html` does, which also follows later changes.
+pub fn apply(theme: Theme) {
+    let theme = match theme {
+        Theme::System if prefers_dark() => Theme::Dark,
+        Theme::System => Theme::Light,
+        theme => theme,
+    };
+    document()
+        .document_element()
+        .unwrap()
+        .set_attribute("data-theme", &theme.to_string())
+        .unwrap();
+}
+
+fn prefers_dark() -> bool {
+    window()
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches())
+}
+
+/// Switches to a theme and keeps it for later visits.
+pub fn set(theme: Theme) {
+    apply(theme);
+    if let Some(storage) = storage() {
+        storage.set_item(KEY, &theme.to_string()).unwrap();
+    }
+}
@@ -0,0 +1,138 @@
+use itertools::Itertools;
+use strum::{Display, EnumString, VariantArray};
+
+use crate::storage::storage;
+
+/// The reader's settings, kept in `localStorage` as `setting value` lines.
+const KEY: &str = "typography";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Size {
+    Small,
+    #[default]
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Spacing {
+    Compact,
+    #[default]
+    Normal,
+    Relaxed,
+}
+
+/// The two faces shipped in `src/fonts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString, VariantArray)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Face {
+    #[default]
+    Serif,
+    Sans,
+}
+
+impl Size {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Small => "S",
+            Self::Medium => "M",
+            Self::Large => "L",
+            Self::ExtraLarge => "XL",
+        }
+    }
+}
+
+impl Spacing {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Compact => "Compact",
+            Self::Normal => "Normal",
+            Self::Relaxed => "Relaxed",
+        }
+    }
+}
+
+impl Face {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Serif => "Serif",
+            Self::Sans => "Sans",
+        }
+    }
+}
+
+/// How the body of an article is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings {
+    pub size: Size,
+    pub spacing: Spacing,
+    pub face: Face,
+    /// Sans with wider letter and word spacing and no drop cap, which many
+    /// readers with dyslexia find easier.
+    pub dyslexic: bool,
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        let saved = storage()
+            .and_then(|storage| storage.get_item(KEY).ok().flatten())
+            .unwrap_or_default();
+        for line in saved.lines() {
+            let Some((key, value)) = line.split_once(' ') else {
+                continue;
+            };
+            match key {
+                "size" => settings.size = value.parse().unwrap_or_default(),
+                "spacing" => settings.spacing = value.parse().unwrap_or_default(),
+                "face" => settings.face = value.parse().unwrap_or_default(),
+                "dyslexic" => settings.dyslexic = value.parse().unwrap_or_default(),
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    pub fn save(self) {
+        if let Some(storage) = storage() {
+            storage
+                .set_item(
+                    KEY,
+                    &[
+                        format!("size {}", self.size),
+                        format!("spacing {}", self.spacing),
+                        format!("face {}", self.face),
+                        format!("dyslexic {}", self.dyslexic),
+                    ]
+                    .iter()
+                    .join("\n"),
+                )
+                .unwrap();
+        }
+    }
+
+    pub fn classes(self) -> String {
+        [
+            match self.size {
+                Size::Small => "text-base sm:text-lg",
+                Size::Medium => "text-lg sm:text-xl",
+                Size::Large => "text-xl sm:text-2xl",
+                Size::ExtraLarge => "text-2xl sm:text-3xl",
+            },
+            match self.spacing {
+                Spacing::Compact => "leading-snug",
+                Spacing::Normal => "leading-[1.6]",
+                Spacing::Relaxed => "leading-loose",
+            },
+            match (self.face, self.dyslexic) {
+                (_, true) => "font-sans tracking-wide [word-spacing:0.16em]",
+                (Face::Serif, false) => "font-serif",
+                (Face::Sans, false) => "font-sans",
+            },
+        ]
+        .join(" ")
+    }
+}
@@ -6,6 +6,21 @@ module.exports = {
   ],
   theme: {
     extend: {
+      colors: {
+        'paper': 'rgb(var(--paper) / <alpha-value>)',
+        'ink': 'rgb(var(--ink) / <alpha-value>)',
+        'muted': 'rgb(var(--muted) / <alpha-value>)',
+        'accent': 'rgb(var(--accent) / <alpha-value>)',
+        'masthead': 'rgb(var(--masthead) / <alpha-value>)',
+        'masthead-ink': 'rgb(var(--masthead-ink) / <alpha-value>)',
+        'rule': 'rgb(var(--rule) / <alpha-value>)',
+        'panel': 'rgb(var(--panel) / <alpha-value>)',
+        'block': 'rgb(var(--block) / <alpha-value>)',
+        'highlight': 'rgb(var(--highlight) / <alpha-value>)',
+        'selected': 'rgb(var(--selected) / <alpha-value>)',
+        'correct': 'rgb(var(--correct) / <alpha-value>)',
+        'incorrect': 'rgb(var(--incorrect) / <alpha-value>)',
+      },
       fontFamily: {
         'blackletter': ["Unifraktur Maguntia"],
         'noto': ["Noto Sans Display"],